@@ -32,17 +32,58 @@ pub mod error {
 
 pub mod Expression {
 
-    use super::{Position, StateMent, Token, TokenRow};
+    use super::error::ParseError;
+    use super::pattern::{
+        ArrayPattern, ExpressionPattern, IdentifierPattern, ObjectPattern, Pattern, PatternElement,
+        PatternProperty,
+    };
+    use super::StateMent::{Arguments, Block, ClassBody, MethodDefinition};
+    use super::{Position, Token, TokenRow};
+    use crate::scanner::TemplatePart;
     use std::fmt::Debug;
 
     pub trait Exp: Debug {
         fn get_position(&self) -> (Position, Position);
+        //是否可以出现在赋值号左边
+        fn is_assignment_target(&self) -> bool {
+            false
+        }
+        //箭头函数的参数和解构赋值的左边先按表达式解析，之后再转换成解构模式
+        //binding 为 true 时只能绑定标识符（参数、声明），否则也可以是成员表达式（赋值）
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            let _ = binding;
+            Err(ParseError::from(self.get_position().0))
+        }
+        fn into_element(self: Box<Self>, binding: bool) -> Result<PatternElement, ParseError> {
+            Ok(PatternElement::new(self.into_pattern(binding)?, None))
+        }
+        fn is_spread(&self) -> bool {
+            false
+        }
+        fn is_arrow_function(&self) -> bool {
+            false
+        }
     }
 
     impl Exp for Box<dyn Exp> {
         fn get_position(&self) -> (Position, Position) {
             (**self).get_position()
         }
+        fn is_assignment_target(&self) -> bool {
+            (**self).is_assignment_target()
+        }
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            (*self).into_pattern(binding)
+        }
+        fn into_element(self: Box<Self>, binding: bool) -> Result<PatternElement, ParseError> {
+            (*self).into_element(binding)
+        }
+        fn is_spread(&self) -> bool {
+            (**self).is_spread()
+        }
+        fn is_arrow_function(&self) -> bool {
+            (**self).is_arrow_function()
+        }
     }
 
     #[derive(Debug)]
@@ -60,6 +101,16 @@ pub mod Expression {
         pub end: Position,
     }
 
+    //++a、a--
+    #[derive(Debug)]
+    pub struct UpdateExpression {
+        pub op: UpdateOperator,
+        pub prefix: bool,
+        pub argument: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
     #[derive(Debug)]
     pub struct BinaryExpression<T: Exp, U: Exp> {
         pub op: BinaryOpeator,
@@ -69,6 +120,33 @@ pub mod Expression {
         pub end: Position,
     }
 
+    #[derive(Debug)]
+    pub struct LogicalExpression<T: Exp, U: Exp> {
+        pub op: LogicalOperator,
+        pub left: T,
+        pub right: U,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ConditionalExpression {
+        pub test: Box<dyn Exp>,
+        pub consequent: Box<dyn Exp>,
+        pub alternate: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct AssignmentExpression {
+        pub op: AssignmentOperator,
+        pub target: Box<dyn Pattern>,
+        pub value: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
     #[derive(Debug)]
     pub struct GroupExpression<T: Exp> {
         pub exp: T,
@@ -76,6 +154,165 @@ pub mod Expression {
         pub end: Position,
     }
 
+    #[derive(Debug)]
+    pub struct ArrayExpression {
+        pub elements: Vec<Option<Box<dyn Exp>>>, //None 表示空位，如 [1, , 2]
+        pub trailing_comma: Option<Position>, //最后一个元素之后的逗号，[...a,] 不能转换成解构模式
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ObjectExpression {
+        pub properties: Vec<ObjectProperty>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub enum ObjectProperty {
+        Property(Property),
+        Method(MethodDefinition), //{a() {}}、{get a() {}}
+        Spread(Box<dyn Exp>),     //{...a}
+    }
+
+    //...a，出现在函数调用的参数、数组和对象字面量中
+    #[derive(Debug)]
+    pub struct SpreadElement {
+        pub argument: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct Property {
+        pub key: Box<dyn Exp>,
+        pub value: Box<dyn Exp>,
+        pub computed: bool,
+        pub shorthand: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //函数和方法的 async、* 标记
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct FunctionFlags {
+        pub is_async: bool,
+        pub is_generator: bool,
+    }
+
+    #[derive(Debug)]
+    pub struct FunctionExpression {
+        pub name: Option<String>,
+        pub args: Arguments,
+        pub body: Block,
+        pub is_async: bool,
+        pub is_generator: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub enum ArrowFunctionBody {
+        Block(Block),
+        Expression(Box<dyn Exp>),
+    }
+
+    #[derive(Debug)]
+    pub struct ArrowFunctionExpression {
+        pub args: Arguments,
+        pub body: ArrowFunctionBody,
+        pub is_async: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct AwaitExpression {
+        pub argument: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //yield、yield a、yield* a
+    #[derive(Debug)]
+    pub struct YieldExpression {
+        pub argument: Option<Box<dyn Exp>>,
+        pub delegate: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ClassExpression {
+        pub name: Option<String>,
+        pub super_class: Option<Box<dyn Exp>>,
+        pub body: ClassBody,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //new Foo(a)，没有参数列表时 arguments 为空，如 new Foo
+    #[derive(Debug)]
+    pub struct NewExpression {
+        pub callee: Box<dyn Exp>,
+        pub arguments: Vec<Box<dyn Exp>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //super 只能出现在 super(...)、super.a、super[a] 中
+    #[derive(Debug)]
+    pub struct Super {
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //模板中的一段字符串，含有非法转义时 cooked 为 None（只出现在带标签的模板中）
+    #[derive(Debug)]
+    pub struct TemplateElement {
+        pub cooked: Option<String>,
+        pub raw: String,
+        pub tail: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //`a${b}c`，quasis 比 expressions 多一个
+    #[derive(Debug)]
+    pub struct TemplateLiteral {
+        pub quasis: Vec<TemplateElement>,
+        pub expressions: Vec<Box<dyn Exp>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //tag`a${b}`
+    #[derive(Debug)]
+    pub struct TaggedTemplateExpression {
+        pub tag: Box<dyn Exp>,
+        pub quasi: TemplateLiteral,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct MemberExpression<T: Exp> {
+        pub object: T,
+        pub property: Box<dyn Exp>,
+        pub computed: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct CallExpression<T: Exp> {
+        pub callee: T,
+        pub arguments: Vec<Box<dyn Exp>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
     // #[derive(Debug)]
     // pub struct Primary {
     //     pub exp: PrimaryRow,
@@ -85,11 +322,24 @@ pub mod Expression {
 
     #[derive(Debug)]
     pub enum PrimaryRow {
-        Number(f64),
-        String(String),
+        Number {
+            value: f64,
+            raw: String, //源码中的原文
+        },
+        BigInt(String), //源码中的原文，包括结尾的 n
+        String {
+            value: String, //转义之后的值
+            raw: String,   //源码中的原文，包括引号
+        },
+        RegExpLiteral {
+            pattern: String,
+            flags: String,
+        },
         True,
         False,
         Null,
+        Identifier(String),
+        This,
     }
 
     #[derive(Debug)]
@@ -112,6 +362,35 @@ pub mod Expression {
         Div,
     }
 
+    //短路运算符，和 BinaryOpeator 分开，右操作数不一定会被求值
+    #[derive(Debug)]
+    pub struct LogicalOperator {
+        pub op: LogicalOperatorRow,
+        pub start: Position,
+        pub end: Position,
+    }
+    #[derive(Debug)]
+    pub enum LogicalOperatorRow {
+        And,
+        Or,
+        Nullish,
+    }
+
+    #[derive(Debug)]
+    pub struct AssignmentOperator {
+        pub op: AssignmentOperatorRow,
+        pub start: Position,
+        pub end: Position,
+    }
+    #[derive(Debug)]
+    pub enum AssignmentOperatorRow {
+        Assign,
+        PlusAssign,
+        MinusAssign,
+        MultipAssign,
+        DivAssign,
+    }
+
     #[derive(Debug)]
     pub struct UnaryOperator {
         pub op: UnaryOperatorRow,
@@ -124,22 +403,234 @@ pub mod Expression {
         Negative,
     }
 
+    #[derive(Debug)]
+    pub struct UpdateOperator {
+        pub op: UpdateOperatorRow,
+        pub start: Position,
+        pub end: Position,
+    }
+    #[derive(Debug)]
+    pub enum UpdateOperatorRow {
+        Increment,
+        Decrement,
+    }
+
     impl Exp for PrimaryExpression {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
+        fn is_assignment_target(&self) -> bool {
+            matches!(self.exp, PrimaryRow::Identifier(_))
+        }
+        fn into_pattern(self: Box<Self>, _binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            match self.exp {
+                PrimaryRow::Identifier(name) => Ok(IdentifierPattern::new(name, self.start)),
+                _ => Err(ParseError::from(self.start)),
+            }
+        }
     }
     impl<T: Exp> Exp for UanryExpression<T> {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
     }
+    impl Exp for UpdateExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
     impl<T: Exp, U: Exp> Exp for BinaryExpression<T, U> {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
     }
-    impl<T: Exp> Exp for GroupExpression<T> {
+    impl<T: Exp, U: Exp> Exp for LogicalExpression<T, U> {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for ConditionalExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for AssignmentExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn into_element(self: Box<Self>, binding: bool) -> Result<PatternElement, ParseError> {
+            //a = 1 作为解构的一项时，表示带默认值
+            match self.op.op {
+                AssignmentOperatorRow::Assign => {
+                    if binding {
+                        self.target.check_binding()?;
+                    }
+                    Ok(PatternElement::new(self.target, Some(self.value)))
+                }
+                _ => Err(ParseError::from(self.op.start)),
+            }
+        }
+    }
+    impl<T: Exp + 'static> Exp for GroupExpression<T> {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn is_assignment_target(&self) -> bool {
+            self.exp.is_assignment_target()
+        }
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            //(a) = 1 可以，({a}) = 1 和参数 (a) 不可以
+            if binding || !self.exp.is_assignment_target() {
+                return Err(ParseError::from(self.start));
+            }
+            let exp: Box<dyn Exp> = Box::new(self.exp);
+            exp.into_pattern(binding)
+        }
+    }
+    impl Exp for ArrayExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            let mut elements = vec![];
+            let mut rest = None;
+            let count = self.elements.len();
+            for (index, element) in self.elements.into_iter().enumerate() {
+                elements.push(match element {
+                    Some(element) if element.is_spread() => {
+                        //[a, ...b] 中的 ...b 只能在最后，后面也不能有逗号
+                        if index + 1 != count {
+                            return Err(ParseError::from(element.get_position().0));
+                        }
+                        if let Some(comma) = self.trailing_comma {
+                            return Err(ParseError::from(comma));
+                        }
+                        rest = Some(element.into_pattern(binding)?);
+                        continue;
+                    }
+                    Some(element) => Some(element.into_element(binding)?),
+                    None => None,
+                });
+            }
+            Ok(ArrayPattern::new(elements, rest, (self.start, self.end)))
+        }
+    }
+    impl Exp for ObjectExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            let mut properties = vec![];
+            let mut rest = None;
+            let count = self.properties.len();
+            for (index, property) in self.properties.into_iter().enumerate() {
+                match property {
+                    ObjectProperty::Property(property) => {
+                        let value = property.value.into_element(binding)?;
+                        properties.push(PatternProperty::new(
+                            property.key,
+                            value,
+                            property.computed,
+                            property.shorthand,
+                            property.start,
+                        ));
+                    }
+                    //方法不能作为解构的目标
+                    ObjectProperty::Method(method) => {
+                        return Err(ParseError::from(method.start));
+                    }
+                    ObjectProperty::Spread(spread) => {
+                        //{a, ...b} 中的 ...b 只能在最后，声明中只能是标识符
+                        let start = spread.get_position().0;
+                        if index + 1 != count {
+                            return Err(ParseError::from(start));
+                        }
+                        let pattern = spread.into_pattern(binding)?;
+                        if binding && !pattern.is_identifier() {
+                            return Err(ParseError::from(start));
+                        }
+                        rest = Some(pattern);
+                    }
+                }
+            }
+            Ok(ObjectPattern::new(properties, rest, (self.start, self.end)))
+        }
+    }
+    impl Exp for SpreadElement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn is_spread(&self) -> bool {
+            true
+        }
+        //作为解构的剩余部分时，转换的是 ... 后面的表达式
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            self.argument.into_pattern(binding)
+        }
+    }
+    impl Exp for FunctionExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for ArrowFunctionExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn is_arrow_function(&self) -> bool {
+            true
+        }
+    }
+    impl Exp for AwaitExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for YieldExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for ClassExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for NewExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for Super {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for TemplateLiteral {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl Exp for TaggedTemplateExpression {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+    }
+    impl<T: Exp + 'static> Exp for MemberExpression<T> {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn is_assignment_target(&self) -> bool {
+            true
+        }
+        fn into_pattern(self: Box<Self>, binding: bool) -> Result<Box<dyn Pattern>, ParseError> {
+            if binding {
+                return Err(ParseError::from(self.start));
+            }
+            Ok(ExpressionPattern::new(self))
+        }
+    }
+    impl<T: Exp> Exp for CallExpression<T> {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
@@ -162,6 +653,28 @@ pub mod Expression {
         }
     }
 
+    impl LogicalOperatorRow {
+        pub fn len(&self) -> usize {
+            match self {
+                &LogicalOperatorRow::And => 2,
+                &LogicalOperatorRow::Or => 2,
+                &LogicalOperatorRow::Nullish => 2,
+            }
+        }
+    }
+
+    impl AssignmentOperatorRow {
+        pub fn len(&self) -> usize {
+            match self {
+                &AssignmentOperatorRow::Assign => 1,
+                &AssignmentOperatorRow::PlusAssign => 2,
+                &AssignmentOperatorRow::MinusAssign => 2,
+                &AssignmentOperatorRow::MultipAssign => 2,
+                &AssignmentOperatorRow::DivAssign => 2,
+            }
+        }
+    }
+
     impl UnaryOperatorRow {
         pub fn len(&self) -> usize {
             match self {
@@ -171,6 +684,12 @@ pub mod Expression {
         }
     }
 
+    impl UpdateOperatorRow {
+        pub fn len(&self) -> usize {
+            2
+        }
+    }
+
     impl BinaryOpeator {
         pub fn new(token: Token) -> Self {
             let op_row = match token.token {
@@ -197,6 +716,40 @@ pub mod Expression {
         }
     }
 
+    impl LogicalOperator {
+        pub fn new(token: Token) -> Self {
+            let op_row = match token.token {
+                TokenRow::DoubleOr => LogicalOperatorRow::Or,
+                TokenRow::DoubleQuestion => LogicalOperatorRow::Nullish,
+                _ => LogicalOperatorRow::And,
+            };
+            let op_len = op_row.len().saturating_sub(1);
+            Self {
+                op: op_row,
+                start: token.position.clone(),
+                end: Position::new(token.position.row, token.position.col + op_len),
+            }
+        }
+    }
+
+    impl AssignmentOperator {
+        pub fn new(token: Token) -> Self {
+            let op_row = match token.token {
+                TokenRow::PlusEq => AssignmentOperatorRow::PlusAssign,
+                TokenRow::MinusEq => AssignmentOperatorRow::MinusAssign,
+                TokenRow::StartEq => AssignmentOperatorRow::MultipAssign,
+                TokenRow::DivEq => AssignmentOperatorRow::DivAssign,
+                _ => AssignmentOperatorRow::Assign,
+            };
+            let op_len = op_row.len().saturating_sub(1);
+            Self {
+                op: op_row,
+                start: token.position.clone(),
+                end: Position::new(token.position.row, token.position.col + op_len),
+            }
+        }
+    }
+
     impl UnaryOperator {
         pub fn new(token: Token) -> Self {
             let op_row = match token.token {
@@ -213,14 +766,35 @@ pub mod Expression {
         }
     }
 
+    impl UpdateOperator {
+        pub fn new(token: Token) -> Self {
+            let op_row = match token.token {
+                TokenRow::Decrement => UpdateOperatorRow::Decrement,
+                _ => UpdateOperatorRow::Increment,
+            };
+            let op_len = op_row.len().saturating_sub(1);
+            Self {
+                op: op_row,
+                start: token.position.clone(),
+                end: Position::new(token.position.row, token.position.col + op_len),
+            }
+        }
+    }
+
     impl PrimaryRow {
         pub fn len(&self) -> usize {
             match self {
-                PrimaryRow::Number(n) => n.to_string().len(),
-                PrimaryRow::String(s) => s.len(),
+                PrimaryRow::Number { raw, .. } => raw.len(),
+                PrimaryRow::BigInt(raw) => raw.len(),
+                PrimaryRow::String { raw, .. } => raw.chars().count(),
+                PrimaryRow::RegExpLiteral { pattern, flags } => {
+                    pattern.chars().count() + flags.len() + 2
+                }
                 PrimaryRow::True => 4,
                 PrimaryRow::False => 4,
                 PrimaryRow::Null => 4,
+                PrimaryRow::Identifier(s) => s.len(),
+                PrimaryRow::This => 4,
             }
         }
     }
@@ -251,6 +825,24 @@ pub mod Expression {
         }
     }
 
+    impl UpdateExpression {
+        pub fn new(op: UpdateOperator, argument: Box<dyn Exp>, prefix: bool) -> Box<dyn Exp> {
+            let (start, end) = if prefix {
+                (op.start.clone(), argument.get_position().1)
+            } else {
+                (argument.get_position().0, op.end.clone())
+            };
+            let e = Self {
+                op,
+                prefix,
+                argument,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
     impl<T: Exp + 'static, U: Exp + 'static> BinaryExpression<T, U> {
         pub fn new(op: BinaryOpeator, left: T, right: U) -> Box<dyn Exp> {
             let start = left.get_position().0;
@@ -266,6 +858,59 @@ pub mod Expression {
         }
     }
 
+    impl<T: Exp + 'static, U: Exp + 'static> LogicalExpression<T, U> {
+        pub fn new(op: LogicalOperator, left: T, right: U) -> Box<dyn Exp> {
+            let start = left.get_position().0;
+            let end = right.get_position().1;
+            let e = Self {
+                op,
+                left,
+                right,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl ConditionalExpression {
+        pub fn new(
+            test: Box<dyn Exp>,
+            consequent: Box<dyn Exp>,
+            alternate: Box<dyn Exp>,
+        ) -> Box<dyn Exp> {
+            let start = test.get_position().0;
+            let end = alternate.get_position().1;
+            let e = Self {
+                test,
+                consequent,
+                alternate,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl AssignmentExpression {
+        pub fn new(
+            op: AssignmentOperator,
+            target: Box<dyn Pattern>,
+            value: Box<dyn Exp>,
+        ) -> Box<dyn Exp> {
+            let start = target.get_position().0;
+            let end = value.get_position().1;
+            let e = Self {
+                op,
+                target,
+                value,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
     impl<T: Exp + 'static> GroupExpression<T> {
         pub fn new(exp: T, position: (Position, Position)) -> Box<dyn Exp> {
             let (start, end) = position;
@@ -273,6 +918,262 @@ pub mod Expression {
             Box::new(e)
         }
     }
+
+    impl ArrayExpression {
+        pub fn new(
+            elements: Vec<Option<Box<dyn Exp>>>,
+            trailing_comma: Option<Position>,
+            position: (Position, Position),
+        ) -> Box<dyn Exp> {
+            let (start, end) = position;
+            let e = Self {
+                elements,
+                trailing_comma,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl ObjectExpression {
+        pub fn new(
+            properties: Vec<ObjectProperty>,
+            position: (Position, Position),
+        ) -> Box<dyn Exp> {
+            let (start, end) = position;
+            let e = Self {
+                properties,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl SpreadElement {
+        pub fn new(start: Position, argument: Box<dyn Exp>) -> Box<dyn Exp> {
+            let end = argument.get_position().1;
+            let e = Self {
+                argument,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl Property {
+        pub fn new(
+            key: Box<dyn Exp>,
+            value: Box<dyn Exp>,
+            computed: bool,
+            shorthand: bool,
+            start: Position,
+        ) -> Self {
+            let end = value.get_position().1;
+            Self {
+                key,
+                value,
+                computed,
+                shorthand,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl FunctionExpression {
+        pub fn new(
+            name: Option<String>,
+            args: Arguments,
+            body: Block,
+            flags: FunctionFlags,
+            position: (Position, Position),
+        ) -> Box<dyn Exp> {
+            let (start, end) = position;
+            let e = Self {
+                name,
+                args,
+                body,
+                is_async: flags.is_async,
+                is_generator: flags.is_generator,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl ArrowFunctionExpression {
+        pub fn new(
+            args: Arguments,
+            body: ArrowFunctionBody,
+            is_async: bool,
+            start: Position,
+        ) -> Box<dyn Exp> {
+            let end = match &body {
+                ArrowFunctionBody::Block(block) => block.end.clone(),
+                ArrowFunctionBody::Expression(exp) => exp.get_position().1,
+            };
+            let e = Self {
+                args,
+                body,
+                is_async,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl AwaitExpression {
+        pub fn new(argument: Box<dyn Exp>, start: Position) -> Box<dyn Exp> {
+            let end = argument.get_position().1;
+            let e = Self {
+                argument,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl YieldExpression {
+        pub fn new(
+            argument: Option<Box<dyn Exp>>,
+            delegate: bool,
+            position: (Position, Position),
+        ) -> Box<dyn Exp> {
+            let (start, end) = position;
+            let e = Self {
+                argument,
+                delegate,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl ClassExpression {
+        pub fn new(
+            name: Option<String>,
+            super_class: Option<Box<dyn Exp>>,
+            body: ClassBody,
+            start: Position,
+        ) -> Box<dyn Exp> {
+            let end = body.end.clone();
+            let e = Self {
+                name,
+                super_class,
+                body,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl NewExpression {
+        pub fn new(
+            callee: Box<dyn Exp>,
+            arguments: Vec<Box<dyn Exp>>,
+            position: (Position, Position),
+        ) -> Box<dyn Exp> {
+            let (start, end) = position;
+            let e = Self {
+                callee,
+                arguments,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl Super {
+        pub fn new(position: Position) -> Box<dyn Exp> {
+            let end = Position::new(position.row, position.col + 4);
+            let e = Self {
+                start: position,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl TemplateElement {
+        pub fn new(part: TemplatePart, tail: bool, start: Position) -> Self {
+            let TemplatePart { cooked, raw, end } = part;
+            Self {
+                cooked,
+                raw,
+                tail,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl TemplateLiteral {
+        pub fn new(quasis: Vec<TemplateElement>, expressions: Vec<Box<dyn Exp>>) -> Self {
+            let start = quasis.first().unwrap().start.clone();
+            let end = quasis.last().unwrap().end.clone();
+            Self {
+                quasis,
+                expressions,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl TaggedTemplateExpression {
+        pub fn new(tag: Box<dyn Exp>, quasi: TemplateLiteral) -> Box<dyn Exp> {
+            let start = tag.get_position().0;
+            let end = quasi.end.clone();
+            let e = Self {
+                tag,
+                quasi,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl<T: Exp + 'static> MemberExpression<T> {
+        pub fn new(
+            object: T,
+            property: Box<dyn Exp>,
+            computed: bool,
+            end: Position,
+        ) -> Box<dyn Exp> {
+            let start = object.get_position().0;
+            let e = Self {
+                object,
+                property,
+                computed,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
+
+    impl<T: Exp + 'static> CallExpression<T> {
+        pub fn new(callee: T, arguments: Vec<Box<dyn Exp>>, end: Position) -> Box<dyn Exp> {
+            let start = callee.get_position().0;
+            let e = Self {
+                callee,
+                arguments,
+                start,
+                end,
+            };
+            Box::new(e)
+        }
+    }
 }
 
 pub mod StateMent {
@@ -280,8 +1181,9 @@ pub mod StateMent {
     use crate::scanner::{Token, TokenRow};
     use std::fmt::Debug;
 
+    use super::pattern::{Pattern, PatternElement};
     use super::right_value::{RightValue, RightValueExpression};
-    use super::Expression::Exp;
+    use super::Expression::{Exp, FunctionFlags};
     use super::Position;
     pub trait StateMent: Debug {
         fn get_position(&self) -> (Position, Position);
@@ -295,18 +1197,25 @@ pub mod StateMent {
         pub end: Position,
     }
 
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum DeclareKind {
+        Let,
+        Const,
+        Var,
+    }
+
     #[derive(Debug)]
     pub struct DeclareStatement {
-        pub variable_name: String,
-        pub value: RightValueExpression,
+        pub kind: DeclareKind,
+        pub declarations: Vec<VariableDeclarator>,
         pub start: Position,
         pub end: Position,
     }
 
     #[derive(Debug)]
-    pub struct AssignStatement {
-        pub variable_name: String,
-        pub value: RightValueExpression,
+    pub struct VariableDeclarator {
+        pub pattern: Box<dyn Pattern>,
+        pub value: Option<RightValueExpression>,
         pub start: Position,
         pub end: Position,
     }
@@ -339,45 +1248,245 @@ pub mod StateMent {
     }
 
     #[derive(Debug)]
-    pub struct ReturnStatement {
-        pub exp: Option<Box<dyn Exp>>,
+    pub struct DoWhileStatement {
+        pub body: Box<dyn StateMent>,
+        pub condition: Box<dyn Exp>,
         pub start: Position,
         pub end: Position,
     }
 
+    //for (let a in b)、for ([a, b] of c) 中 in/of 左边的部分
     #[derive(Debug)]
-    pub struct Block {
-        pub body: Vec<Box<dyn StateMent>>,
-        pub start: Position,
-        pub end: Position,
-    }
-    impl Default for Block {
-        fn default() -> Self {
-            Self {
-                body: Default::default(),
-                start: Default::default(),
-                end: Default::default(),
-            }
-        }
+    pub enum ForLeft {
+        Declaration(DeclareStatement),
+        Pattern(Box<dyn Pattern>),
     }
 
     #[derive(Debug)]
-    pub struct FunctionDeclareStatement {
+    pub struct ForInStatement {
+        pub left: ForLeft,
+        pub right: Box<dyn Exp>,
+        pub body: Box<dyn StateMent>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ForOfStatement {
+        pub left: ForLeft,
+        pub right: Box<dyn Exp>,
+        pub body: Box<dyn StateMent>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ReturnStatement {
+        pub exp: Option<Box<dyn Exp>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct BreakStatement {
+        pub label: Option<String>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ContinueStatement {
+        pub label: Option<String>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct LabeledStatement {
+        pub label: String,
+        pub body: Box<dyn StateMent>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct SwitchStatement {
+        pub discriminant: Box<dyn Exp>,
+        pub cases: Vec<SwitchCase>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //default 的 test 为 None，没有 break 的 case 会继续执行下一个 case
+    #[derive(Debug)]
+    pub struct SwitchCase {
+        pub test: Option<Box<dyn Exp>>,
+        pub consequent: Vec<Box<dyn StateMent>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ThrowStatement {
+        pub argument: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct TryStatement {
+        pub block: Block,
+        pub handler: Option<CatchClause>,
+        pub finalizer: Option<Block>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //catch (e) {}，param 可以省略：catch {}
+    #[derive(Debug)]
+    pub struct CatchClause {
+        pub param: Option<Box<dyn Pattern>>,
+        pub body: Block,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct Block {
+        pub body: Vec<Box<dyn StateMent>>,
+        pub start: Position,
+        pub end: Position,
+    }
+    impl Default for Block {
+        fn default() -> Self {
+            Self {
+                body: Default::default(),
+                start: Default::default(),
+                end: Default::default(),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct FunctionDeclareStatement {
+        pub name: String,
+        pub args: Arguments,
+        pub body: Block,
+        pub is_async: bool,
+        pub is_generator: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+    #[derive(Debug)]
+    pub struct ClassDeclaration {
         pub name: String,
+        pub super_class: Option<Box<dyn Exp>>,
+        pub body: ClassBody,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ClassBody {
+        pub members: Vec<ClassMember>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub enum ClassMember {
+        Method(MethodDefinition),
+        Field(FieldDefinition),
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum MethodKind {
+        Constructor,
+        Method,
+        Get,
+        Set,
+    }
+
+    #[derive(Debug)]
+    pub struct MethodDefinition {
+        pub key: Box<dyn Exp>,
+        pub computed: bool,
+        pub kind: MethodKind,
+        pub is_static: bool,
+        pub is_async: bool,
+        pub is_generator: bool,
         pub args: Arguments,
         pub body: Block,
         pub start: Position,
         pub end: Position,
     }
+
+    //类的字段 a = 1; 或者 static a;
+    #[derive(Debug)]
+    pub struct FieldDefinition {
+        pub key: Box<dyn Exp>,
+        pub computed: bool,
+        pub is_static: bool,
+        pub value: Option<Box<dyn Exp>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ImportDeclaration {
+        pub specifiers: Vec<ImportSpecifier>,
+        pub source: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub enum ImportSpecifier {
+        Default(String),                           //import a from "m"
+        Namespace(String),                         //import * as a from "m"
+        Named { imported: String, local: String }, //import { a as b } from "m"
+    }
+
+    #[derive(Debug)]
+    pub struct ExportDeclaration {
+        pub kind: ExportKind,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub enum ExportKind {
+        Declaration(Box<dyn StateMent>), //export let a = 1、export function f() {}
+        Default {
+            value: Box<dyn Exp>,
+            is_expression: bool, //export default function () {} 后面不需要分号
+        },
+        Named {
+            specifiers: Vec<ExportSpecifier>,
+            source: Option<Box<dyn Exp>>,
+        },
+        All {
+            exported: Option<String>, //export * as a from "m"
+            source: Box<dyn Exp>,
+        },
+    }
+
+    #[derive(Debug)]
+    pub struct ExportSpecifier {
+        pub local: String,
+        pub exported: String,
+    }
+
     #[derive(Debug)]
     pub struct Arguments {
-        pub args: Vec<String>,
+        pub args: Vec<Param>,
         pub position: Option<(Position, Position)>,
     }
     #[derive(Debug)]
-    pub struct FunctionCall {
-        pub function_name: String,
-        pub arguments: Arguments,
+    pub struct Param {
+        pub pattern: Box<dyn Pattern>,
+        pub default: Option<Box<dyn Exp>>,
+        pub rest: bool, //...args
         pub start: Position,
         pub end: Position,
     }
@@ -389,38 +1498,42 @@ pub mod StateMent {
         }
     }
 
+    impl DeclareKind {
+        pub fn new(token: &TokenRow) -> Self {
+            match token {
+                TokenRow::Const => DeclareKind::Const,
+                TokenRow::Var => DeclareKind::Var,
+                _ => DeclareKind::Let,
+            }
+        }
+    }
+
     impl DeclareStatement {
         pub fn new(
-            variable_token: TokenRow,
-            value: RightValueExpression,
+            kind: DeclareKind,
+            declarations: Vec<VariableDeclarator>,
             position: (Position, Position),
         ) -> Self {
             Self {
-                variable_name: match variable_token {
-                    TokenRow::Identifier(s) => s,
-                    _ => Default::default(),
-                },
-                value,
+                kind,
+                declarations,
                 start: position.0,
                 end: position.1,
             }
         }
     }
 
-    impl AssignStatement {
-        pub fn new(
-            variable_token: TokenRow,
-            value: RightValueExpression,
-            position: (Position, Position),
-        ) -> Self {
+    impl VariableDeclarator {
+        pub fn new(pattern: Box<dyn Pattern>, value: Option<RightValueExpression>) -> Self {
+            let (start, mut end) = pattern.get_position();
+            if let Some(value) = &value {
+                end = value.0.get_position().1;
+            }
             Self {
-                variable_name: match variable_token {
-                    TokenRow::Identifier(s) => s,
-                    _ => Default::default(),
-                },
+                pattern,
                 value,
-                start: position.0,
-                end: position.1,
+                start,
+                end,
             }
         }
     }
@@ -476,6 +1589,57 @@ pub mod StateMent {
         }
     }
 
+    impl DoWhileStatement {
+        pub fn new(
+            body: Box<dyn StateMent>,
+            condition: Box<dyn Exp>,
+            position: (Position, Position),
+        ) -> Self {
+            Self {
+                body,
+                condition,
+                start: position.0,
+                end: position.1,
+            }
+        }
+    }
+
+    impl ForInStatement {
+        pub fn new(
+            left: ForLeft,
+            right: Box<dyn Exp>,
+            body: Box<dyn StateMent>,
+            start: Position,
+        ) -> Self {
+            let end = body.get_position().1;
+            Self {
+                left,
+                right,
+                body,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl ForOfStatement {
+        pub fn new(
+            left: ForLeft,
+            right: Box<dyn Exp>,
+            body: Box<dyn StateMent>,
+            start: Position,
+        ) -> Self {
+            let end = body.get_position().1;
+            Self {
+                left,
+                right,
+                body,
+                start,
+                end,
+            }
+        }
+    }
+
     impl ReturnStatement {
         pub fn new(exp: Option<Box<dyn Exp>>, position: (Position, Position)) -> Self {
             Self {
@@ -486,6 +1650,117 @@ pub mod StateMent {
         }
     }
 
+    impl BreakStatement {
+        pub fn new(label: Option<String>, position: (Position, Position)) -> Self {
+            Self {
+                label,
+                start: position.0,
+                end: position.1,
+            }
+        }
+    }
+
+    impl ContinueStatement {
+        pub fn new(label: Option<String>, position: (Position, Position)) -> Self {
+            Self {
+                label,
+                start: position.0,
+                end: position.1,
+            }
+        }
+    }
+
+    impl LabeledStatement {
+        pub fn new(label: String, body: Box<dyn StateMent>, start: Position) -> Self {
+            let end = body.get_position().1;
+            Self {
+                label,
+                body,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl SwitchStatement {
+        pub fn new(
+            discriminant: Box<dyn Exp>,
+            cases: Vec<SwitchCase>,
+            position: (Position, Position),
+        ) -> Self {
+            Self {
+                discriminant,
+                cases,
+                start: position.0,
+                end: position.1,
+            }
+        }
+    }
+
+    impl SwitchCase {
+        pub fn new(
+            test: Option<Box<dyn Exp>>,
+            consequent: Vec<Box<dyn StateMent>>,
+            position: (Position, Position),
+        ) -> Self {
+            let (start, mut end) = position;
+            if let Some(last) = consequent.last() {
+                end = last.get_position().1;
+            }
+            Self {
+                test,
+                consequent,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl ThrowStatement {
+        pub fn new(argument: Box<dyn Exp>, start: Position) -> Self {
+            let end = argument.get_position().1;
+            Self {
+                argument,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl TryStatement {
+        pub fn new(
+            block: Block,
+            handler: Option<CatchClause>,
+            finalizer: Option<Block>,
+            start: Position,
+        ) -> Self {
+            let end = match (&handler, &finalizer) {
+                (_, Some(finalizer)) => finalizer.end.clone(),
+                (Some(handler), None) => handler.end.clone(),
+                (None, None) => block.end.clone(),
+            };
+            Self {
+                block,
+                handler,
+                finalizer,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl CatchClause {
+        pub fn new(param: Option<Box<dyn Pattern>>, body: Block, start: Position) -> Self {
+            let end = body.end.clone();
+            Self {
+                param,
+                body,
+                start,
+                end,
+            }
+        }
+    }
+
     impl Block {
         pub fn new(statements: Vec<Box<dyn StateMent>>, position: (Position, Position)) -> Self {
             Self {
@@ -501,35 +1776,146 @@ pub mod StateMent {
             name: String,
             args: Arguments,
             body: Block,
+            flags: FunctionFlags,
             position: (Position, Position),
         ) -> Self {
             Self {
                 name,
                 args,
                 body,
+                is_async: flags.is_async,
+                is_generator: flags.is_generator,
                 start: position.0,
                 end: position.1,
             }
         }
     }
 
-    impl Arguments {
-        pub fn new(args: Vec<String>, position: Option<(Position, Position)>) -> Self {
-            Self { args, position }
+    impl ClassDeclaration {
+        pub fn new(
+            name: String,
+            super_class: Option<Box<dyn Exp>>,
+            body: ClassBody,
+            start: Position,
+        ) -> Self {
+            let end = body.end.clone();
+            Self {
+                name,
+                super_class,
+                body,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl ClassBody {
+        pub fn new(members: Vec<ClassMember>, position: (Position, Position)) -> Self {
+            Self {
+                members,
+                start: position.0,
+                end: position.1,
+            }
+        }
+    }
+
+    impl MethodDefinition {
+        pub fn new(
+            key: Box<dyn Exp>,
+            computed: bool,
+            kind: MethodKind,
+            flags: FunctionFlags,
+            args: Arguments,
+            body: Block,
+            start: Position,
+        ) -> Self {
+            let end = body.end.clone();
+            Self {
+                key,
+                computed,
+                kind,
+                is_static: false,
+                is_async: flags.is_async,
+                is_generator: flags.is_generator,
+                args,
+                body,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl FieldDefinition {
+        pub fn new(
+            key: Box<dyn Exp>,
+            computed: bool,
+            is_static: bool,
+            value: Option<Box<dyn Exp>>,
+            start: Position,
+        ) -> Self {
+            let end = match &value {
+                Some(value) => value.get_position().1,
+                None => key.get_position().1,
+            };
+            Self {
+                key,
+                computed,
+                is_static,
+                value,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl ImportDeclaration {
+        pub fn new(
+            specifiers: Vec<ImportSpecifier>,
+            source: Box<dyn Exp>,
+            start: Position,
+        ) -> Self {
+            let end = source.get_position().1;
+            Self {
+                specifiers,
+                source,
+                start,
+                end,
+            }
         }
     }
 
-    impl FunctionCall {
-        pub fn new(name: String, args: Arguments, position: (Position, Position)) -> Self {
+    impl ExportDeclaration {
+        pub fn new(kind: ExportKind, position: (Position, Position)) -> Self {
             Self {
-                function_name: name,
-                arguments: args,
+                kind,
                 start: position.0,
                 end: position.1,
             }
         }
     }
 
+    impl Arguments {
+        pub fn new(args: Vec<Param>) -> Self {
+            let position = match (args.first(), args.last()) {
+                (Some(first), Some(last)) => Some((first.start.clone(), last.end.clone())),
+                _ => None,
+            };
+            Self { args, position }
+        }
+    }
+
+    impl Param {
+        pub fn new(element: PatternElement, rest: bool, start: Position) -> Self {
+            Self {
+                pattern: element.target,
+                default: element.default,
+                rest,
+                start,
+                end: element.end,
+            }
+        }
+    }
+
     impl StateMent for ExpressionStatement {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
@@ -548,16 +1934,16 @@ pub mod StateMent {
             true
         }
     }
-    impl StateMent for AssignStatement {
+    impl StateMent for IfStatement {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
 
         fn need_semi(&self) -> bool {
-            true
+            false
         }
     }
-    impl StateMent for IfStatement {
+    impl StateMent for WhileStatement {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
@@ -566,7 +1952,7 @@ pub mod StateMent {
             false
         }
     }
-    impl StateMent for WhileStatement {
+    impl StateMent for ForStatement {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
@@ -575,7 +1961,25 @@ pub mod StateMent {
             false
         }
     }
-    impl StateMent for ForStatement {
+    impl StateMent for DoWhileStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        //do {} while (a) 后面的分号可以省略
+        fn need_semi(&self) -> bool {
+            false
+        }
+    }
+    impl StateMent for ForInStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            false
+        }
+    }
+    impl StateMent for ForOfStatement {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
@@ -593,6 +1997,60 @@ pub mod StateMent {
             true
         }
     }
+    impl StateMent for BreakStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            true
+        }
+    }
+    impl StateMent for ContinueStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            true
+        }
+    }
+    impl StateMent for LabeledStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            self.body.need_semi()
+        }
+    }
+    impl StateMent for SwitchStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            false
+        }
+    }
+    impl StateMent for ThrowStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            true
+        }
+    }
+    impl StateMent for TryStatement {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            false
+        }
+    }
     impl StateMent for Block {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
@@ -611,22 +2069,243 @@ pub mod StateMent {
             false
         }
     }
-    impl StateMent for FunctionCall {
+    impl StateMent for ImportDeclaration {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            true
+        }
+    }
+    impl StateMent for ExportDeclaration {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+
+        fn need_semi(&self) -> bool {
+            match &self.kind {
+                ExportKind::Declaration(declaration) => declaration.need_semi(),
+                ExportKind::Default { is_expression, .. } => *is_expression,
+                _ => true,
+            }
+        }
+    }
+    impl StateMent for ClassDeclaration {
         fn get_position(&self) -> (Position, Position) {
             (self.start.clone(), self.end.clone())
         }
 
         fn need_semi(&self) -> bool {
+            false
+        }
+    }
+}
+
+pub mod pattern {
+    use std::fmt::Debug;
+
+    use super::error::ParseError;
+    use super::Expression::Exp;
+    use super::Position;
+
+    //解构时等号左边的结构：标识符、{ } 或者 [ ]
+    pub trait Pattern: Debug {
+        fn get_position(&self) -> (Position, Position);
+        //参数和声明中只能绑定标识符，不能出现 a.b 这样的赋值目标
+        fn check_binding(&self) -> Result<(), ParseError> {
+            Ok(())
+        }
+        fn is_identifier(&self) -> bool {
+            false
+        }
+    }
+
+    impl Pattern for Box<dyn Pattern> {
+        fn get_position(&self) -> (Position, Position) {
+            (**self).get_position()
+        }
+        fn check_binding(&self) -> Result<(), ParseError> {
+            (**self).check_binding()
+        }
+        fn is_identifier(&self) -> bool {
+            (**self).is_identifier()
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct IdentifierPattern {
+        pub name: String,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //解构赋值中的成员表达式，如 [a.b, c[0]] = arr
+    #[derive(Debug)]
+    pub struct ExpressionPattern {
+        pub exp: Box<dyn Exp>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ObjectPattern {
+        pub properties: Vec<PatternProperty>,
+        pub rest: Option<Box<dyn Pattern>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct PatternProperty {
+        pub key: Box<dyn Exp>,
+        pub value: PatternElement,
+        pub computed: bool,
+        pub shorthand: bool,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    #[derive(Debug)]
+    pub struct ArrayPattern {
+        pub elements: Vec<Option<PatternElement>>, //None 表示跳过的位置，如 [, b]
+        pub rest: Option<Box<dyn Pattern>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    //解构的一项，可以带默认值：a = 1
+    #[derive(Debug)]
+    pub struct PatternElement {
+        pub target: Box<dyn Pattern>,
+        pub default: Option<Box<dyn Exp>>,
+        pub start: Position,
+        pub end: Position,
+    }
+
+    impl Pattern for IdentifierPattern {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn is_identifier(&self) -> bool {
             true
         }
     }
-    impl From<AssignStatement> for DeclareStatement {
-        fn from(assing_statement: AssignStatement) -> Self {
+    impl Pattern for ExpressionPattern {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn check_binding(&self) -> Result<(), ParseError> {
+            Err(ParseError::from(self.start.clone()))
+        }
+    }
+    impl Pattern for ObjectPattern {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn check_binding(&self) -> Result<(), ParseError> {
+            for property in self.properties.iter() {
+                property.value.target.check_binding()?;
+            }
+            match &self.rest {
+                Some(rest) => rest.check_binding(),
+                None => Ok(()),
+            }
+        }
+    }
+    impl Pattern for ArrayPattern {
+        fn get_position(&self) -> (Position, Position) {
+            (self.start.clone(), self.end.clone())
+        }
+        fn check_binding(&self) -> Result<(), ParseError> {
+            for element in self.elements.iter().flatten() {
+                element.target.check_binding()?;
+            }
+            match &self.rest {
+                Some(rest) => rest.check_binding(),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl IdentifierPattern {
+        pub fn new(name: String, start: Position) -> Box<dyn Pattern> {
+            let end = Position::new(start.row, start.col + name.len().saturating_sub(1));
+            Box::new(Self { name, start, end })
+        }
+    }
+
+    impl ExpressionPattern {
+        pub fn new(exp: Box<dyn Exp>) -> Box<dyn Pattern> {
+            let (start, end) = exp.get_position();
+            Box::new(Self { exp, start, end })
+        }
+    }
+
+    impl ObjectPattern {
+        pub fn new(
+            properties: Vec<PatternProperty>,
+            rest: Option<Box<dyn Pattern>>,
+            position: (Position, Position),
+        ) -> Box<dyn Pattern> {
+            let (start, end) = position;
+            Box::new(Self {
+                properties,
+                rest,
+                start,
+                end,
+            })
+        }
+    }
+
+    impl PatternProperty {
+        pub fn new(
+            key: Box<dyn Exp>,
+            value: PatternElement,
+            computed: bool,
+            shorthand: bool,
+            start: Position,
+        ) -> Self {
+            let end = value.end.clone();
             Self {
-                variable_name: assing_statement.variable_name,
-                value: assing_statement.value,
-                start: assing_statement.start,
-                end: assing_statement.end,
+                key,
+                value,
+                computed,
+                shorthand,
+                start,
+                end,
+            }
+        }
+    }
+
+    impl ArrayPattern {
+        pub fn new(
+            elements: Vec<Option<PatternElement>>,
+            rest: Option<Box<dyn Pattern>>,
+            position: (Position, Position),
+        ) -> Box<dyn Pattern> {
+            let (start, end) = position;
+            Box::new(Self {
+                elements,
+                rest,
+                start,
+                end,
+            })
+        }
+    }
+
+    impl PatternElement {
+        pub fn new(target: Box<dyn Pattern>, default: Option<Box<dyn Exp>>) -> Self {
+            let start = target.get_position().0;
+            let end = match &default {
+                Some(default) => default.get_position().1,
+                None => target.get_position().1,
+            };
+            Self {
+                target,
+                default,
+                start,
+                end,
             }
         }
     }
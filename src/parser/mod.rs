@@ -1,73 +1,186 @@
 pub mod ast;
-use self::ast::{
-    error::ParseError, right_value::RightValue, Expression::PrimaryRow, StateMent::FunctionCall,
-};
+use self::ast::{error::ParseError, right_value::RightValue, Expression::PrimaryRow};
 use super::error::{NoContentError, SyntaxError as AllError};
-use super::scanner::{error::ScanError, Position, Scanner, Token, TokenRow};
+use super::scanner::{error::ScanError, Goal, Position, Scanner, Token, TokenRow};
+use ast::pattern::{
+    ArrayPattern, IdentifierPattern, ObjectPattern, Pattern, PatternElement, PatternProperty,
+};
 use ast::right_value::RightValueExpression;
 use ast::Expression::{
-    BinaryExpression, BinaryOpeator, Exp, GroupExpression, PrimaryExpression, UanryExpression,
-    UnaryOperator,
+    ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression, AssignmentExpression,
+    AssignmentOperator, AwaitExpression, BinaryExpression, BinaryOpeator, CallExpression,
+    ClassExpression, ConditionalExpression, Exp, FunctionExpression, FunctionFlags,
+    GroupExpression, LogicalExpression, LogicalOperator, MemberExpression, NewExpression,
+    ObjectExpression, ObjectProperty, PrimaryExpression, Property, SpreadElement, Super,
+    TaggedTemplateExpression, TemplateElement, TemplateLiteral, UanryExpression, UnaryOperator,
+    UpdateExpression, UpdateOperator, YieldExpression,
 };
 use ast::StateMent::{
-    Arguments, AssignStatement, Block, DeclareStatement, ExpressionStatement, ForStatement,
-    FunctionDeclareStatement, IfStatement, ReturnStatement, StateMent, WhileStatement,
+    Arguments, Block, BreakStatement, CatchClause, ClassBody, ClassDeclaration, ClassMember,
+    ContinueStatement, DeclareKind, DeclareStatement, DoWhileStatement, ExportDeclaration,
+    ExportKind, ExportSpecifier, ExpressionStatement, FieldDefinition, ForInStatement, ForLeft,
+    ForOfStatement, ForStatement, FunctionDeclareStatement, IfStatement, ImportDeclaration,
+    ImportSpecifier, LabeledStatement, MethodDefinition, MethodKind, Param, ReturnStatement,
+    StateMent, SwitchCase, SwitchStatement, ThrowStatement, TryStatement, VariableDeclarator,
+    WhileStatement,
 };
 use ast::Value::Value;
+use std::collections::HashMap;
+
+//当前函数体内的上下文，进入新的函数时重置
+#[derive(Debug, Default)]
+struct Context {
+    labels: Vec<(String, bool)>, //(标签名, 是否标记循环)
+    loop_depth: usize,
+    switch_depth: usize,
+    super_property: bool,             //类的方法中可以使用 super.a
+    super_call: bool,                 //有父类的 constructor 中可以调用 super()
+    in_async: bool,                   //async 函数和模块顶层中 await 是关键字
+    in_generator: bool,               //generator 函数中 yield 是关键字
+    await_or_yield: Option<Position>, //括号中的 await、yield，括号转换成箭头函数的参数时是错误的
+}
+
+impl Context {
+    //箭头函数没有自己的 super，沿用外层的；箭头函数不能是 generator
+    fn arrow(&self, is_async: bool) -> Self {
+        Self {
+            super_property: self.super_property,
+            super_call: self.super_call,
+            in_async: is_async,
+            ..Default::default()
+        }
+    }
+}
+
+//脚本或者模块，只有模块中可以使用 import 和 export
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SourceType {
+    #[default]
+    Script,
+    Module,
+}
 
 #[derive(Debug)]
-pub struct Parser {
-    pub token_list: Result<Vec<Token>, ScanError>,
+pub struct Parser<'a> {
+    pub token_list: Vec<Token>,
+    //解析器向前看时才继续扫描，扫描结束或者出错之后为 None
+    scanner: Option<Scanner<'a>>,
+    //扫描出错时保留错误之前的 token，解析到出错的位置才报告
+    scan_error: Option<ScanError>,
+    //还没有读过的 / 和 /= 开始扫描之前的扫描器，在允许正则的位置从这里重新扫描
+    checkpoints: HashMap<usize, Scanner<'a>>,
     index: usize,
+    //赋值表达式开头的 ( 和 async( 已经解析过但不是箭头函数，由 primary 返回
+    parsed_primary: Option<Box<dyn Exp>>,
+    //{a = 1} 中 = 的位置，只有转换成解构模式时才合法，否则在表达式结束时报错
+    cover_initializer: Option<Position>,
+    context: Context,
+    source_type: SourceType,
 }
 
-impl<'a> Parser {
+impl<'a> Parser<'a> {
     pub fn new(s: &'a str) -> Self {
-        let scanner = Scanner::new(s);
-        let token_list = scanner
-            .filter(|token| match token {
-                Ok(Token {
-                    token: TokenRow::Space(_),
-                    ..
-                }) => false,
-                _ => true,
-            })
-            .collect();
+        Self::with_source_type(s, SourceType::Script)
+    }
+
+    pub fn with_source_type(s: &'a str, source_type: SourceType) -> Self {
         Self {
-            token_list,
+            token_list: vec![],
+            scanner: Some(Scanner::new(s)),
+            scan_error: None,
+            checkpoints: HashMap::new(),
             index: 0,
+            parsed_primary: None,
+            cover_initializer: None,
+            context: Context {
+                in_async: source_type == SourceType::Module,
+                ..Default::default()
+            },
+            source_type,
         }
     }
 
+    fn fill(&mut self, n: usize) {
+        //按需扫描，直到向前看的第 n 个 token 已经扫描出来，空白和注释会被过滤掉
+        while self.token_list.len() <= self.index + n {
+            let scanner = match self.scanner.as_mut() {
+                Some(scanner) => scanner,
+                None => return,
+            };
+            let checkpoint = if scanner.next_is_expected('/') {
+                Some(scanner.clone())
+            } else {
+                None
+            };
+            match scanner.next() {
+                None => self.scanner = None,
+                Some(Err(e)) => {
+                    self.scan_error = Some(e);
+                    self.scanner = None;
+                }
+                Some(Ok(Token {
+                    token: TokenRow::Space(_) | TokenRow::Comment { .. },
+                    ..
+                })) => {}
+                Some(Ok(token)) => {
+                    if let (Some(checkpoint), TokenRow::Div | TokenRow::DivEq) =
+                        (checkpoint, &token.token)
+                    {
+                        self.checkpoints.insert(self.token_list.len(), checkpoint);
+                    }
+                    self.token_list.push(token);
+                }
+            }
+        }
+    }
+
+    fn rescan(&mut self, goal: Goal) {
+        //按新的词法目标重新扫描当前的 token，向前看时多扫描的 token 也要丢掉
+        let mut scanner = match self.checkpoints.remove(&self.index) {
+            Some(scanner) => scanner,
+            None => return,
+        };
+        scanner.set_goal(goal);
+        self.token_list.truncate(self.index);
+        self.checkpoints.clear();
+        self.scan_error = None;
+        self.scanner = Some(scanner);
+    }
+
     pub fn advance(&mut self) -> Result<Option<Token>, ScanError> {
         if self.is_end()? {
             return Ok(None);
         }
-        let token_list = self.token_list.as_ref();
-        let list = match token_list {
-            Ok(list) => list,
-            Err(e) => return Err(e.clone()),
-        };
-        let token = list[self.index].clone();
+        let token = self.token_list[self.index].clone();
+        self.checkpoints.remove(&self.index);
         self.index += 1;
-        Ok(Some(token.clone()))
+        Ok(Some(token))
     }
 
-    pub fn is_end(&self) -> Result<bool, ScanError> {
-        match self.token_list.as_ref() {
-            Err(e) => Err(e.clone()),
-            Ok(list) => Ok(self.index >= list.len()),
+    pub fn is_end(&mut self) -> Result<bool, ScanError> {
+        self.fill(0);
+        if self.index < self.token_list.len() {
+            return Ok(false);
+        }
+        match &self.scan_error {
+            Some(e) => Err(e.clone()),
+            None => Ok(true),
         }
     }
 
-    pub fn peek_n(&self, n: usize) -> Result<Option<&Token>, ScanError> {
-        match self.token_list.as_ref() {
-            Err(e) => Err(e.clone()),
-            Ok(list) => Ok(list.get(self.index + n)),
+    pub fn peek_n(&mut self, n: usize) -> Result<Option<&Token>, ScanError> {
+        self.fill(n);
+        match self.token_list.get(self.index + n) {
+            Some(token) => Ok(Some(token)),
+            //向前看时不报告扫描错误，后面的 token 可能会按正则重新扫描
+            None => match &self.scan_error {
+                Some(e) if n == 0 => Err(e.clone()),
+                _ => Ok(None),
+            },
         }
     }
 
-    pub fn next_n_match(&self, match_list: Vec<TokenRow>) -> Result<bool, AllError> {
+    pub fn next_n_match(&mut self, match_list: Vec<TokenRow>) -> Result<bool, AllError> {
         let token = self.peek_n(0)?;
         match token {
             Some(v) => Ok(match_list.iter().any(|x| v.token == *x)),
@@ -75,7 +188,7 @@ impl<'a> Parser {
         }
     }
 
-    pub fn expect(&self, position: Position, expected: TokenRow) -> Result<bool, AllError> {
+    pub fn expect(&mut self, position: Position, expected: TokenRow) -> Result<bool, AllError> {
         let position = Position {
             col: position.col + 1,
             ..position
@@ -83,10 +196,7 @@ impl<'a> Parser {
         let next = self.peek_n(0)?;
         let s = expected.to_string();
         match next {
-            Some(Token {
-                token: _expected,
-                position: _,
-            }) => Ok(true),
+            Some(Token { token, .. }) if *token == expected => Ok(true),
             _ => Err(ParseError {
                 code: 400,
                 position,
@@ -96,19 +206,244 @@ impl<'a> Parser {
         }
     }
 
-    pub fn next_n_is(&self, n: usize, match_list: Vec<TokenRow>) -> Result<bool, AllError> {
+    pub fn next_n_is(&mut self, n: usize, match_list: Vec<TokenRow>) -> Result<bool, AllError> {
         let n = self.peek_n(n)?;
         match n {
             Some(Token {
                 token: t,
                 position: _p,
+                ..
             }) => Ok(match_list.iter().any(|token| token == t)),
             _ => Ok(false),
         }
     }
 
     pub fn expresson(&mut self) -> Result<Box<dyn Exp>, AllError> {
-        self.equality()
+        self.assignment()
+    }
+
+    pub fn assignment(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let outer = self.cover_initializer.take();
+        let exp = self.assignment_cover()?;
+        self.check_cover_initializer(outer)?;
+        Ok(exp)
+    }
+
+    fn assignment_cover(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        //和 assignment 相同，但是 {a = 1} 的错误留给外层，外层可能会把它转换成解构模式
+        if self.context.in_generator
+            && self.next_n_match(vec![TokenRow::Identifier("yield".to_string())])?
+        {
+            return self.yield_expression();
+        }
+        if let Some(arrow) = self.arrow_function_or_primary()? {
+            return Ok(arrow);
+        }
+        let outer = self.cover_initializer.take();
+        let target = self.conditional()?;
+        let pending = std::mem::replace(&mut self.cover_initializer, outer);
+        if let Some(position) = pending.clone() {
+            //{a = 1}.b 这样的成员表达式不会被转换成解构模式
+            if target.is_assignment_target() {
+                return Err(Self::cover_initializer_error(position));
+            }
+        }
+        if self.next_n_match(vec![
+            TokenRow::Eq,
+            TokenRow::PlusEq,
+            TokenRow::MinusEq,
+            TokenRow::StartEq,
+            TokenRow::DivEq,
+        ])? {
+            let start = target.get_position().0;
+            let op = self.advance()?.unwrap();
+            //复合赋值 += 等只能作用于标识符和成员表达式，= 的左边还可以是解构模式
+            if op.token != TokenRow::Eq && !target.is_assignment_target() {
+                return Err(ParseError {
+                    code: 400,
+                    describe: "invalid assignment target".to_string(),
+                    position: start,
+                }
+                .into());
+            }
+            let target = target.into_pattern(false).map_err(|error| ParseError {
+                describe: "invalid assignment target".to_string(),
+                ..error
+            })?;
+            let op = AssignmentOperator::new(op);
+            //赋值是右结合的：a = b = 1
+            let value = self.assignment()?;
+            return Ok(AssignmentExpression::new(op, target, value));
+        }
+        if self.cover_initializer.is_none() {
+            self.cover_initializer = pending;
+        }
+        Ok(target)
+    }
+
+    fn arrow_function_or_primary(&mut self) -> Result<Option<Box<dyn Exp>>, AllError> {
+        //箭头函数只能出现在赋值表达式的开头，并且就是整个赋值表达式：a + x => x、() => {}(1) 都是错误的
+        //( 和 async( 要解析完才知道是不是箭头函数，不是的话交给 primary，继续解析后面的运算
+        let exp = match self.peek_n(0)?.cloned() {
+            Some(
+                token @ Token {
+                    token: TokenRow::Identifier(_),
+                    ..
+                },
+            ) if self.next_is_arrow(1)? => {
+                self.check_reserved(&token)?;
+                self.advance()?;
+                let position = token.position.clone();
+                let pattern = IdentifierPattern::new(token.to_string(), position.clone());
+                let param = Param::new(PatternElement::new(pattern, None), false, position);
+                let args = Arguments::new(vec![param]);
+                return Ok(Some(self.arrow_function(args, false, token.position)?));
+            }
+            Some(Token {
+                token: TokenRow::Identifier(name),
+                ..
+            }) if name == "async" => self.async_function_or_arrow(true)?,
+            Some(Token {
+                token: TokenRow::LeftParent,
+                ..
+            }) => Some(self.group(true)?),
+            _ => None,
+        };
+        match exp {
+            Some(exp) if exp.is_arrow_function() => Ok(Some(exp)),
+            exp => {
+                self.parsed_primary = exp;
+                Ok(None)
+            }
+        }
+    }
+
+    fn check_cover_initializer(&mut self, outer: Option<Position>) -> Result<(), AllError> {
+        //恢复外层的记录，当前这一层没有被转换成解构模式的 {a = 1} 是错误的
+        match std::mem::replace(&mut self.cover_initializer, outer) {
+            Some(position) => Err(Self::cover_initializer_error(position)),
+            None => Ok(()),
+        }
+    }
+
+    fn check_await_or_yield(
+        &mut self,
+        outer: Option<Position>,
+        params: bool,
+    ) -> Result<(), AllError> {
+        //恢复外层的记录，括号被转换成箭头函数的参数时其中不能有 await、yield，如 (a = yield) => a
+        let inner = std::mem::replace(&mut self.context.await_or_yield, outer);
+        match inner {
+            Some(position) if params => Err(ParseError {
+                code: 400,
+                describe: "arrow function parameters must not contain await or yield".to_string(),
+                position,
+            }
+            .into()),
+            inner => {
+                self.context.await_or_yield = self.context.await_or_yield.take().or(inner);
+                Ok(())
+            }
+        }
+    }
+
+    fn cover_initializer_error(position: Position) -> AllError {
+        ParseError {
+            code: 400,
+            describe: "invalid shorthand property initializer".to_string(),
+            position,
+        }
+        .into()
+    }
+
+    fn yield_expression(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let yield_token = self.advance()?.unwrap();
+        let position = yield_token.position.clone();
+        self.context.await_or_yield.get_or_insert(position);
+        let mut end = Position::new(yield_token.position.row, yield_token.position.col + 4);
+        let mut delegate = false;
+        if self.next_n_match(vec![TokenRow::Start])? {
+            end = self.advance()?.unwrap().position;
+            delegate = true;
+        }
+        //yield 后面没有表达式时，下一个 token 只能是这些结束符
+        let argument = if !delegate
+            && (self.is_end()?
+                || self.line_break_before(0)?
+                || self.next_n_match(vec![
+                    TokenRow::RightParent,
+                    TokenRow::RightBracket,
+                    TokenRow::RightBrace,
+                    TokenRow::Comma,
+                    TokenRow::Semicolon,
+                    TokenRow::Colon,
+                ])?) {
+            None
+        } else {
+            let argument = self.assignment()?;
+            end = argument.get_position().1;
+            Some(argument)
+        };
+        Ok(YieldExpression::new(
+            argument,
+            delegate,
+            (yield_token.position, end),
+        ))
+    }
+
+    pub fn conditional(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let test = self.logical_or()?;
+        if !self.next_n_match(vec![TokenRow::Question])? {
+            return Ok(test);
+        }
+        self.advance()?;
+        let consequent = self.assignment()?;
+        self.expect(consequent.get_position().1, TokenRow::Colon)?;
+        self.advance()?;
+        //右结合：a ? b : c ? d : e
+        let alternate = self.assignment()?;
+        Ok(ConditionalExpression::new(test, consequent, alternate))
+    }
+
+    pub fn logical_or(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        //?? 和 &&、|| 混用时必须加括号，如 (a || b) ?? c
+        let (mut left, mut mixed) = self.logical_and()?;
+        let mut nullish = false;
+        while self.next_n_match(vec![TokenRow::DoubleOr, TokenRow::DoubleQuestion])? {
+            let op = self.advance()?.unwrap();
+            let (right, has_and) = self.logical_and()?;
+            if op.token == TokenRow::DoubleQuestion {
+                nullish = true;
+            } else {
+                mixed = true;
+            }
+            if nullish && (mixed || has_and) {
+                return Err(ParseError {
+                    code: 400,
+                    describe: "\"??\" cannot be mixed with \"&&\" or \"||\" without parentheses"
+                        .to_string(),
+                    position: op.position,
+                }
+                .into());
+            }
+            let op = LogicalOperator::new(op);
+            left = LogicalExpression::new(op, left, right);
+        }
+        Ok(left)
+    }
+
+    fn logical_and(&mut self) -> Result<(Box<dyn Exp>, bool), AllError> {
+        //第二个返回值表示是否出现了 &&
+        let mut left = self.equality()?;
+        let mut has_and = false;
+        while self.next_n_match(vec![TokenRow::DoubleAnd])? {
+            let op = self.advance()?.unwrap();
+            let right = self.equality()?;
+            let op = LogicalOperator::new(op);
+            left = LogicalExpression::new(op, left, right);
+            has_and = true;
+        }
+        Ok((left, has_and))
     }
 
     pub fn equality(&mut self) -> Result<Box<dyn Exp>, AllError> {
@@ -162,6 +497,24 @@ impl<'a> Parser {
     }
 
     pub fn unary(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        if self.parsed_primary.is_some() {
+            return self.postfix();
+        }
+        if self.context.in_async
+            && self.next_n_match(vec![TokenRow::Identifier("await".to_string())])?
+        {
+            let await_token = self.advance()?.unwrap();
+            let position = await_token.position.clone();
+            self.context.await_or_yield.get_or_insert(position);
+            let argument = self.unary()?;
+            return Ok(AwaitExpression::new(argument, await_token.position));
+        }
+        if self.next_n_match(vec![TokenRow::Increment, TokenRow::Decrement])? {
+            let op = UpdateOperator::new(self.advance()?.unwrap());
+            let argument = self.unary()?;
+            Self::check_update_target(argument.as_ref())?;
+            return Ok(UpdateExpression::new(op, argument, true));
+        }
         if self.next_n_match(vec![TokenRow::Minus, TokenRow::Exclamation])? {
             let mut exp: Result<Box<dyn Exp>, AllError> = Err(NoContentError::new().into());
             if self.next_n_match(vec![TokenRow::Minus, TokenRow::Exclamation])? {
@@ -172,24 +525,189 @@ impl<'a> Parser {
             }
             exp
         } else {
-            self.primary()
+            self.postfix()
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let argument = self.call()?;
+        //a\n++b 是两条语句，后缀 ++ 前面不能换行
+        if self.next_n_match(vec![TokenRow::Increment, TokenRow::Decrement])?
+            && !self.line_break_before(0)?
+        {
+            Self::check_update_target(argument.as_ref())?;
+            let op = UpdateOperator::new(self.advance()?.unwrap());
+            return Ok(UpdateExpression::new(op, argument, false));
+        }
+        Ok(argument)
+    }
+
+    fn check_update_target(argument: &dyn Exp) -> Result<(), AllError> {
+        if !argument.is_assignment_target() {
+            return Err(ParseError {
+                code: 400,
+                describe: "invalid left-hand side expression in update operation".to_string(),
+                position: argument.get_position().0,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    pub fn call(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let callee = if self.parsed_primary.is_none() && self.next_n_match(vec![TokenRow::New])? {
+            self.new_expression()?
+        } else {
+            self.primary()?
+        };
+        self.call_tail(callee, true)
+    }
+
+    fn new_expression(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        //new a.b(c) 中的 (c) 属于 new，new a.b 也是合法的
+        let new_token = self.advance()?.unwrap();
+        let callee = if self.next_n_match(vec![TokenRow::New])? {
+            self.new_expression()?
+        } else {
+            self.primary()?
+        };
+        let callee = self.call_tail(callee, false)?;
+        let (arguments, end) = if self.next_n_match(vec![TokenRow::LeftParent])? {
+            self.call_arguments(callee.get_position().1)?
+        } else {
+            (vec![], callee.get_position().1)
+        };
+        Ok(NewExpression::new(
+            callee,
+            arguments,
+            (new_token.position, end),
+        ))
+    }
+
+    fn call_arguments(
+        &mut self,
+        position: Position,
+    ) -> Result<(Vec<Box<dyn Exp>>, Position), AllError> {
+        let outer = self.cover_initializer.take();
+        let arguments = self.cover_call_arguments(position)?;
+        self.check_cover_initializer(outer)?;
+        Ok(arguments)
+    }
+
+    fn cover_call_arguments(
+        &mut self,
+        position: Position,
+    ) -> Result<(Vec<Box<dyn Exp>>, Position), AllError> {
+        //async(a, b) 的参数可能会被转换成箭头函数的参数
+        self.advance()?;
+        let arguments = self.get_arguments()?;
+        let end = match arguments.last() {
+            Some(arg) => arg.get_position().1,
+            None => position,
+        };
+        self.expect(end, TokenRow::RightParent)?;
+        let right_parent = self.advance()?.unwrap();
+        Ok((arguments, right_parent.position))
+    }
+
+    fn call_tail(
+        &mut self,
+        mut callee: Box<dyn Exp>,
+        call: bool,
+    ) -> Result<Box<dyn Exp>, AllError> {
+        loop {
+            if call && self.next_n_match(vec![TokenRow::LeftParent])? {
+                let (arguments, end) = self.call_arguments(callee.get_position().1)?;
+                callee = CallExpression::new(callee, arguments, end);
+            } else if self.next_n_match(vec![TokenRow::Dot])? {
+                let dot = self.advance()?.unwrap();
+                let property = self.property_identifier(dot.position)?;
+                let end = property.get_position().1;
+                callee = MemberExpression::new(callee, property, false, end);
+            } else if self.next_n_match(vec![TokenRow::LeftBracket])? {
+                self.advance()?;
+                let property = self.expresson()?;
+                self.expect(property.get_position().1, TokenRow::RightBracket)?;
+                let right_bracket = self.advance()?.unwrap();
+                callee = MemberExpression::new(callee, property, true, right_bracket.position);
+            } else if let Some(Token {
+                token: TokenRow::TemplateString(_) | TokenRow::TemplateHead(_),
+                ..
+            }) = self.peek_n(0)?
+            {
+                let quasi = self.template_literal(true)?;
+                callee = TaggedTemplateExpression::new(callee, quasi);
+            } else {
+                break;
+            }
+        }
+        Ok(callee)
+    }
+
+    fn property_identifier(&mut self, position: Position) -> Result<Box<dyn Exp>, AllError> {
+        //`.`之后的属性名，关键字也可以作为属性名
+        match self.advance()? {
+            Some(token) if token.is_identifier_name() => Ok(PrimaryExpression::new(
+                PrimaryRow::Identifier(token.to_string()),
+                token.position,
+            )),
+            Some(token) => Err(ParseError::from(token).into()),
+            None => Err(ParseError {
+                code: 400,
+                describe: "property name is expected".to_string(),
+                position: Position {
+                    row: position.row,
+                    col: position.col + 1,
+                },
+            }
+            .into()),
         }
     }
 
     pub fn primary(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        if let Some(exp) = self.parsed_primary.take() {
+            return Ok(exp);
+        }
+        if self.next_n_match(vec![TokenRow::Identifier("async".to_string())])? {
+            if let Some(exp) = self.async_function_or_arrow(false)? {
+                return Ok(exp);
+            }
+        }
+        if let Some(token) = self.peek_n(0)?.cloned() {
+            self.check_reserved(&token)?;
+        }
+        //表达式开头的 / 是正则而不是除号
+        if self.next_n_match(vec![TokenRow::Div, TokenRow::DivEq])? {
+            self.rescan(Goal::RegExp);
+        }
         let next_token = self.peek_n(0)?;
         let prim = match next_token {
             Some(v) => match v.clone() {
                 Token {
                     token: t,
                     position: p,
+                    ..
                 } => match t {
-                    TokenRow::Digital(n) => PrimaryRow::Number(n),
-                    TokenRow::String(ref s) => PrimaryRow::String(s.clone()),
+                    TokenRow::Digital { value, raw } => PrimaryRow::Number { value, raw },
+                    TokenRow::BigInt(raw) => PrimaryRow::BigInt(raw),
+                    TokenRow::String { value, raw } => PrimaryRow::String { value, raw },
+                    TokenRow::RegExp { pattern, flags } => {
+                        PrimaryRow::RegExpLiteral { pattern, flags }
+                    }
                     TokenRow::True => PrimaryRow::True,
                     TokenRow::False => PrimaryRow::False,
                     TokenRow::Null => PrimaryRow::Null,
-                    TokenRow::LeftParent => return self.group(),
+                    TokenRow::Identifier(ref s) => PrimaryRow::Identifier(s.clone()),
+                    TokenRow::This => PrimaryRow::This,
+                    TokenRow::Super => return self.super_expression(),
+                    TokenRow::Class => return self.class_expression(),
+                    TokenRow::LeftParent => return self.group(false),
+                    TokenRow::LeftBracket => return self.array(),
+                    TokenRow::LeftBrace => return self.object(),
+                    TokenRow::Function => return self.function_expression(),
+                    TokenRow::TemplateString(_) | TokenRow::TemplateHead(_) => {
+                        return Ok(Box::new(self.template_literal(false)?))
+                    }
                     _ => return Err(ParseError::from(p).into()),
                 },
             },
@@ -200,131 +718,640 @@ impl<'a> Parser {
         Ok(exp)
     }
 
-    pub fn group(&mut self) -> Result<Box<dyn Exp>, AllError> {
-        let left_parent = self.advance()?.unwrap();
-        let exp = self.expresson()?;
-        if self.next_n_match(vec![TokenRow::RightParent])? {
-            let right_parent = self.advance()?.unwrap();
-            return Ok(GroupExpression::new(
-                exp,
-                (left_parent.position, right_parent.position),
-            ));
-        } else {
-            let next_token = self.peek_n(0)?;
-            match next_token {
-                None => Err(NoContentError::new().into()),
-                Some(res) => Err(ParseError::from(res.position.clone()).into()),
+    fn template_literal(&mut self, tagged: bool) -> Result<TemplateLiteral, AllError> {
+        //tagged 为 true 时允许非法转义，对应的 cooked 为 None
+        let token = self.advance()?.unwrap();
+        let (mut part, mut position, mut tail) = match token.token {
+            TokenRow::TemplateString(part) => (part, token.position, true),
+            TokenRow::TemplateHead(part) => (part, token.position, false),
+            _ => return Err(ParseError::from(token.position).into()),
+        };
+        let mut quasis = vec![];
+        let mut expressions = vec![];
+        loop {
+            if !tagged && part.cooked.is_none() {
+                return Err(ParseError {
+                    code: 400,
+                    describe: "invalid escape sequence in template".to_string(),
+                    position,
+                }
+                .into());
             }
+            quasis.push(TemplateElement::new(part, tail, position));
+            if tail {
+                break;
+            }
+            let expression = self.expresson()?;
+            let end = expression.get_position().1;
+            expressions.push(expression);
+            (part, position, tail) = match self.advance()? {
+                Some(Token {
+                    token: TokenRow::TemplateMiddle(part),
+                    position,
+                    ..
+                }) => (part, position, false),
+                Some(Token {
+                    token: TokenRow::TemplateTail(part),
+                    position,
+                    ..
+                }) => (part, position, true),
+                Some(token) => return Err(ParseError::from(token).into()),
+                None => {
+                    return Err(ParseError {
+                        code: 400,
+                        describe: r#""}" is expected"#.to_string(),
+                        position: Position::new(end.row, end.col + 1),
+                    }
+                    .into())
+                }
+            };
         }
+        Ok(TemplateLiteral::new(quasis, expressions))
     }
 
-    pub fn statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        match self.peek_n(0)? {
-            None => todo!(),
-            Some(res) => match res.clone() {
-                Token {
-                    token: t,
-                    position: _p,
-                } => match t {
-                    TokenRow::Let => self.declare_statement(),
-                    TokenRow::Identifier(_ident) => match self.peek_n(1)? {
-                        Some(Token { token: t, position }) => match t {
-                            TokenRow::Eq => self.assign_statement(),
-                            TokenRow::LeftParent => self.function_call_statement(),
-                            _ => self.expression_statement(),
-                        },
-                        _ => self.expression_statement(),
-                    },
-                    TokenRow::If => self.if_statement(),
-                    TokenRow::For => self.for_statement(),
-                    TokenRow::While => self.while_statement(),
-                    TokenRow::Return => self.return_statement(),
-                    TokenRow::LeftBrace => self.block(),
-                    TokenRow::Function => self.function_declare_statement(),
-                    _ => self.expression_statement(),
-                },
-            },
+    fn super_expression(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let super_token = self.advance()?.unwrap();
+        let allowed = if self.next_n_match(vec![TokenRow::LeftParent])? {
+            self.context.super_call
+        } else if self.next_n_match(vec![TokenRow::Dot, TokenRow::LeftBracket])? {
+            self.context.super_property
+        } else {
+            false
+        };
+        if !allowed {
+            return Err(ParseError {
+                code: 400,
+                describe: "'super' keyword unexpected here".to_string(),
+                position: super_token.position,
+            }
+            .into());
         }
+        Ok(Super::new(super_token.position))
     }
 
-    pub fn right_value(&mut self) -> Result<RightValueExpression, AllError> {
-        match self.peek_n(0)? {
-            Some(v) => match v {
-                Token {
-                    token: _t,
-                    position: _p,
-                } => (Ok(self.expresson()?.into())),
-            },
-            None => Err(NoContentError::new().into()),
+    pub fn array(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let left_bracket = self.advance()?.unwrap();
+        let mut elements = vec![];
+        let mut trailing_comma = None;
+        while !self.next_n_match(vec![TokenRow::RightBracket])? {
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+                elements.push(None);
+                continue;
+            }
+            let element = self.spread_or_expression()?;
+            let end = element.get_position().1;
+            elements.push(Some(element));
+            trailing_comma = None;
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                trailing_comma = Some(self.advance()?.unwrap().position);
+            } else {
+                self.expect(end, TokenRow::RightBracket)?;
+            }
         }
+        let right_bracket = self.advance()?.unwrap();
+        Ok(ArrayExpression::new(
+            elements,
+            trailing_comma,
+            (left_bracket.position, right_bracket.position),
+        ))
     }
 
-    pub fn declare_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        let let_ident = self.advance()?.unwrap();
-        let assign_statement = self.assign_statement_row()?;
-        let mut declare_statement: DeclareStatement = assign_statement.into();
-        declare_statement.start = let_ident.position;
-        Ok(Box::new(declare_statement))
+    pub fn object(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let left_brace = self.advance()?.unwrap();
+        let mut properties = vec![];
+        while !self.next_n_match(vec![TokenRow::RightBrace])? {
+            let property = if self.next_n_match(vec![TokenRow::Ellipsis])? {
+                ObjectProperty::Spread(self.spread_or_expression()?)
+            } else {
+                self.property()?
+            };
+            let end = match &property {
+                ObjectProperty::Property(property) => property.end.clone(),
+                ObjectProperty::Method(method) => method.end.clone(),
+                ObjectProperty::Spread(spread) => spread.get_position().1,
+            };
+            properties.push(property);
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+            } else {
+                self.expect(end, TokenRow::RightBrace)?;
+            }
+        }
+        let right_brace = self.advance()?.unwrap();
+        Ok(ObjectExpression::new(
+            properties,
+            (left_brace.position, right_brace.position),
+        ))
     }
 
-    pub fn assign_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        let assign_statement_row = self.assign_statement_row()?;
-        Ok(Box::new(assign_statement_row))
+    fn spread_or_expression(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        if self.next_n_match(vec![TokenRow::Ellipsis])? {
+            let ellipsis = self.advance()?.unwrap();
+            let argument = self.assignment_cover()?;
+            return Ok(SpreadElement::new(ellipsis.position, argument));
+        }
+        self.assignment_cover()
     }
 
-    fn assign_statement_row(&mut self) -> Result<AssignStatement, AllError> {
-        let variable_token = self.advance()?.unwrap();
+    fn property(&mut self) -> Result<ObjectProperty, AllError> {
+        let start = self.peek_n(0)?.unwrap().position.clone();
+        //get、set、async 后面紧跟 ( , : = } 时是普通的属性名，如 {get: 1}
+        let name_end = vec![
+            TokenRow::LeftParent,
+            TokenRow::Comma,
+            TokenRow::Colon,
+            TokenRow::Eq,
+            TokenRow::RightBrace,
+        ];
+        let (flags, kind) = self.method_prefix(name_end)?;
+        let is_method = kind != MethodKind::Method || flags.is_async || flags.is_generator;
+        if !is_method {
+            if let Some(property) = self.shorthand_property()? {
+                return Ok(ObjectProperty::Property(property));
+            }
+        }
+        let (key, computed, key_start) = self.property_key()?;
+        if is_method || self.next_n_match(vec![TokenRow::LeftParent])? {
+            let key = (key, computed, key_start);
+            let method = self.method_definition(key, kind, flags, false, start)?;
+            return Ok(ObjectProperty::Method(method));
+        }
+        let key_end = key.get_position().1;
+        self.expect(key_end, TokenRow::Colon)?;
         self.advance()?;
-        let right_value = self.right_value()?;
-        let value_end = right_value.0.get_position().1;
-        Ok(AssignStatement::new(
-            variable_token.token,
-            right_value,
-            (variable_token.position, value_end),
-        ))
+        let value = self.assignment_cover()?;
+        let property = Property::new(key, value, computed, false, key_start);
+        Ok(ObjectProperty::Property(property))
     }
 
-    pub fn if_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        let if_token = self.advance()?.unwrap();
-        self.expect(if_token.position.clone(), TokenRow::LeftParent)?;
-        self.advance()?;
-        let condition = self.expresson()?;
-        self.expect(if_token.position.clone(), TokenRow::RightParent)?;
-        self.advance()?;
-        let then_branch = self.statement()?;
-        let mut end_position = then_branch.get_position().1;
-        let mut else_branch = None;
-        if self.next_n_match(vec![TokenRow::Else])? {
-            self.advance()?.unwrap();
-            let _else_branch = self.statement()?;
-            end_position = _else_branch.get_position().1;
-            else_branch = Some(_else_branch);
+    fn shorthand_property(&mut self) -> Result<Option<Property>, AllError> {
+        if let Some(Token {
+            token: TokenRow::Identifier(name),
+            position,
+            ..
+        }) = self.peek_n(0)?.cloned()
+        {
+            //简写属性 {a, b}，只有标识符可以简写
+            if self.next_n_is(1, vec![TokenRow::Comma, TokenRow::RightBrace])? {
+                self.advance()?;
+                let key =
+                    PrimaryExpression::new(PrimaryRow::Identifier(name.clone()), position.clone());
+                let value = PrimaryExpression::new(PrimaryRow::Identifier(name), position.clone());
+                return Ok(Some(Property::new(key, value, false, true, position)));
+            }
+            //{a = 1} 只能作为解构模式出现，如箭头函数的参数 ({a = 1}) => a
+            if self.next_n_is(1, vec![TokenRow::Eq])? {
+                self.advance()?;
+                let key =
+                    PrimaryExpression::new(PrimaryRow::Identifier(name.clone()), position.clone());
+                let target = IdentifierPattern::new(name, position.clone());
+                let op = self.advance()?.unwrap();
+                self.cover_initializer.get_or_insert(op.position.clone());
+                let op = AssignmentOperator::new(op);
+                let default = self.assignment()?;
+                let value = AssignmentExpression::new(op, target, default);
+                return Ok(Some(Property::new(key, value, false, true, position)));
+            }
         }
-        let if_statement = IfStatement::new(
-            condition,
-            then_branch,
-            else_branch,
-            (if_token.position, end_position),
-        );
-        Ok(Box::new(if_statement))
+        Ok(None)
     }
 
-    pub fn for_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        let for_token = self.advance()?.unwrap();
-        let start = for_token.position.clone();
-
-        self.expect(for_token.position, TokenRow::LeftParent)?;
-        self.advance()?;
+    fn property_key(&mut self) -> Result<(Box<dyn Exp>, bool, Position), AllError> {
+        //属性名：标识符（包括关键字）、字符串、数字或者 [表达式]
+        match self.advance()? {
+            Some(Token {
+                token: TokenRow::LeftBracket,
+                position,
+                ..
+            }) => {
+                let key = self.expresson()?;
+                self.expect(key.get_position().1, TokenRow::RightBracket)?;
+                self.advance()?;
+                Ok((key, true, position))
+            }
+            Some(Token {
+                token: TokenRow::String { value, raw },
+                position,
+                ..
+            }) => Ok((
+                PrimaryExpression::new(PrimaryRow::String { value, raw }, position.clone()),
+                false,
+                position,
+            )),
+            Some(Token {
+                token: TokenRow::Digital { value, raw },
+                position,
+                ..
+            }) => Ok((
+                PrimaryExpression::new(PrimaryRow::Number { value, raw }, position.clone()),
+                false,
+                position,
+            )),
+            Some(token) if token.is_identifier_name() => Ok((
+                PrimaryExpression::new(
+                    PrimaryRow::Identifier(token.to_string()),
+                    token.position.clone(),
+                ),
+                false,
+                token.position,
+            )),
+            Some(token) => Err(ParseError::from(token).into()),
+            None => Err(NoContentError::new().into()),
+        }
+    }
 
-        let mut init_statement: Option<Box<dyn StateMent>> = None;
-        if !self.next_n_is(0, vec![TokenRow::Semicolon])? {
-            let _init_statement = self.statement()?;
-            self.expect(_init_statement.get_position().1, TokenRow::Semicolon)?;
+    pub fn group(&mut self, arrow: bool) -> Result<Box<dyn Exp>, AllError> {
+        //( 之后可能是括号表达式，也可能是箭头函数的参数列表，先按表达式列表解析
+        //arrow 为 false 时不在赋值表达式的开头，不能是箭头函数
+        let left_parent = self.advance()?.unwrap();
+        let outer = self.cover_initializer.take();
+        let outer_await_or_yield = self.context.await_or_yield.take();
+        let mut exps = vec![];
+        let mut rest = None;
+        let mut comma = None;
+        while !self.next_n_match(vec![TokenRow::RightParent])? {
+            if self.next_n_match(vec![TokenRow::Ellipsis])? {
+                //...rest 只能出现在参数列表的最后
+                let param = self.rest_param()?;
+                self.expect(param.end.clone(), TokenRow::RightParent)?;
+                rest = Some(param);
+                break;
+            }
+            let exp = self.assignment_cover()?;
+            let end = exp.get_position().1;
+            exps.push(exp);
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                let token = self.advance()?.unwrap();
+                comma.get_or_insert(token.position);
+            } else {
+                self.expect(end, TokenRow::RightParent)?;
+            }
+        }
+        let right_parent = self.advance()?.unwrap();
+        if arrow && self.next_is_arrow(0)? {
+            self.cover_initializer = outer;
+            self.check_await_or_yield(outer_await_or_yield, true)?;
+            let mut args = vec![];
+            for exp in exps {
+                let start = exp.get_position().0;
+                args.push(Param::new(exp.into_element(true)?, false, start));
+            }
+            args.extend(rest);
+            return self.arrow_function(Arguments::new(args), false, left_parent.position);
+        }
+        self.check_cover_initializer(outer)?;
+        self.check_await_or_yield(outer_await_or_yield, false)?;
+        if let Some(rest) = rest {
+            return Err(ParseError::from(rest.start).into());
+        }
+        match (exps.pop(), comma) {
+            (Some(exp), None) => Ok(GroupExpression::new(
+                exp,
+                (left_parent.position, right_parent.position),
+            )),
+            (Some(_), Some(comma)) => Err(ParseError::from(comma).into()),
+            (None, _) => Err(ParseError::from(right_parent).into()),
+        }
+    }
+
+    fn async_function_or_arrow(&mut self, arrow: bool) -> Result<Option<Box<dyn Exp>>, AllError> {
+        //async 不是关键字：async function、async x => x、async (x) => x 之外都是普通标识符
+        if self.line_break_before(1)? {
+            return Ok(None);
+        }
+        if self.next_n_is(1, vec![TokenRow::Function])? {
+            return Ok(Some(self.function_expression()?));
+        }
+        if self.next_n_is(1, vec![TokenRow::LeftParent])? {
+            //async(a, b) 可能是函数调用，先按参数列表解析，看到 => 再转换成箭头函数的参数
+            let async_token = self.advance()?.unwrap();
+            let outer = self.cover_initializer.take();
+            let outer_await_or_yield = self.context.await_or_yield.take();
+            let (arguments, end) = self.cover_call_arguments(async_token.position.clone())?;
+            if !arrow || !self.next_is_arrow(0)? {
+                self.check_cover_initializer(outer)?;
+                self.check_await_or_yield(outer_await_or_yield, false)?;
+                let callee = PrimaryExpression::new(
+                    PrimaryRow::Identifier(async_token.to_string()),
+                    async_token.position,
+                );
+                return Ok(Some(CallExpression::new(callee, arguments, end)));
+            }
+            self.cover_initializer = outer;
+            self.check_await_or_yield(outer_await_or_yield, true)?;
+            let mut args = vec![];
+            let count = arguments.len();
+            for (index, argument) in arguments.into_iter().enumerate() {
+                let start = argument.get_position().0;
+                let rest = argument.is_spread();
+                if rest && index + 1 != count {
+                    return Err(ParseError::from(start).into());
+                }
+                args.push(Param::new(argument.into_element(true)?, rest, start));
+            }
+            let exp = self.arrow_function(Arguments::new(args), true, async_token.position)?;
+            return Ok(Some(exp));
+        }
+        if let Some(Token {
+            token: TokenRow::Identifier(_),
+            ..
+        }) = self.peek_n(1)?
+        {
+            if arrow && self.next_is_arrow(2)? {
+                let async_token = self.advance()?.unwrap();
+                let param = self.advance()?.unwrap();
+                self.check_reserved(&param)?;
+                let pattern = IdentifierPattern::new(param.to_string(), param.position.clone());
+                let param = Param::new(PatternElement::new(pattern, None), false, param.position);
+                let exp =
+                    self.arrow_function(Arguments::new(vec![param]), true, async_token.position)?;
+                return Ok(Some(exp));
+            }
+        }
+        Ok(None)
+    }
+
+    fn check_reserved(&self, token: &Token) -> Result<(), AllError> {
+        //await、yield 在 async/generator 函数（以及模块）中不能作为标识符
+        let reserved = match &token.token {
+            TokenRow::Identifier(name) if name == "await" => {
+                self.context.in_async || self.source_type == SourceType::Module
+            }
+            TokenRow::Identifier(name) if name == "yield" => self.context.in_generator,
+            _ => false,
+        };
+        if reserved {
+            return Err(ParseError {
+                code: 400,
+                describe: format!(r#"unexpected reserved word "{}""#, token.to_string()),
+                position: token.position.clone(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn next_is_async_function(&mut self) -> Result<bool, AllError> {
+        Ok(
+            self.next_n_match(vec![TokenRow::Identifier("async".to_string())])?
+                && self.next_n_is(1, vec![TokenRow::Function])?
+                && !self.line_break_before(1)?,
+        )
+    }
+
+    fn function_flags(&mut self) -> Result<FunctionFlags, AllError> {
+        //[async] function [*]，function 关键字也会被跳过
+        let mut flags = FunctionFlags::default();
+        if self.next_n_match(vec![TokenRow::Identifier("async".to_string())])? {
+            self.advance()?;
+            flags.is_async = true;
+        }
+        self.advance()?;
+        if self.next_n_match(vec![TokenRow::Start])? {
             self.advance()?;
-            init_statement = Some(_init_statement);
+            flags.is_generator = true;
+        }
+        Ok(flags)
+    }
+
+    fn arrow_function(
+        &mut self,
+        args: Arguments,
+        is_async: bool,
+        start: Position,
+    ) -> Result<Box<dyn Exp>, AllError> {
+        self.advance()?;
+        let context = self.context.arrow(is_async);
+        let body = if self.next_n_match(vec![TokenRow::LeftBrace])? {
+            ArrowFunctionBody::Block(self.body_with(context)?)
         } else {
+            let outer = std::mem::replace(&mut self.context, context);
+            let exp = self.assignment();
+            self.context = outer;
+            ArrowFunctionBody::Expression(exp?)
+        };
+        Ok(ArrowFunctionExpression::new(args, body, is_async, start))
+    }
+
+    pub fn function_expression(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let start = self.peek_n(0)?.unwrap().position.clone();
+        let flags = self.function_flags()?;
+        let mut name = None;
+        let mut position = start.clone();
+        if let Some(Token {
+            token: TokenRow::Identifier(_),
+            ..
+        }) = self.peek_n(0)?
+        {
+            let name_token = self.advance()?.unwrap();
+            position = name_token.position.clone();
+            name = Some(name_token.to_string());
+        }
+        let args = self.function_params(position)?;
+        let body = self.function_body(flags)?;
+        let end = body.get_position().1;
+        Ok(FunctionExpression::new(
+            name,
+            args,
+            body,
+            flags,
+            (start, end),
+        ))
+    }
+
+    pub fn statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        match self.peek_n(0)? {
+            None => Err(NoContentError::new().into()),
+            Some(res) => match res.clone() {
+                Token {
+                    token: t,
+                    position: _p,
+                    ..
+                } => match t {
+                    TokenRow::Let | TokenRow::Const | TokenRow::Var => self.declare_statement(),
+                    TokenRow::If => self.if_statement(),
+                    TokenRow::For => self.for_statement(),
+                    TokenRow::While => self.while_statement(),
+                    TokenRow::Do => self.do_while_statement(),
+                    TokenRow::Return => self.return_statement(),
+                    TokenRow::Break => self.break_statement(),
+                    TokenRow::Continue => self.continue_statement(),
+                    TokenRow::Throw => self.throw_statement(),
+                    TokenRow::Try => self.try_statement(),
+                    TokenRow::Switch => self.switch_statement(),
+                    TokenRow::Identifier(_) if self.next_n_is(1, vec![TokenRow::Colon])? => {
+                        self.labeled_statement()
+                    }
+                    //语句开头的 { 总是代码块，对象字面量只出现在表达式中，如 ({a: 1})
+                    TokenRow::LeftBrace => self.block(),
+                    TokenRow::Function => self.function_declare_statement(),
+                    TokenRow::Identifier(_) if self.next_is_async_function()? => {
+                        self.function_declare_statement()
+                    }
+                    TokenRow::Class => self.class_declaration(),
+                    TokenRow::Import | TokenRow::Export => {
+                        //顶层的 import/export 由 module_item 处理，走到这里说明位置不对
+                        let describe = if self.source_type == SourceType::Module {
+                            format!(
+                                "{} may only appear at the top level of a module",
+                                t.to_string()
+                            )
+                        } else {
+                            format!("cannot use {} outside a module", t.to_string())
+                        };
+                        Err(ParseError {
+                            code: 400,
+                            describe,
+                            position: _p,
+                        }
+                        .into())
+                    }
+                    _ => self.expression_statement(),
+                },
+            },
+        }
+    }
+
+    pub fn right_value(&mut self) -> Result<RightValueExpression, AllError> {
+        match self.peek_n(0)? {
+            Some(v) => match v {
+                Token {
+                    token: _t,
+                    position: _p,
+                    ..
+                } => (Ok(self.expresson()?.into())),
+            },
+            None => Err(NoContentError::new().into()),
+        }
+    }
+
+    pub fn declare_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let kind_token = self.advance()?.unwrap();
+        let pattern = self.binding_target()?;
+        let declare_statement = self.declarations(kind_token, pattern)?;
+        Ok(Box::new(declare_statement))
+    }
+
+    fn declarations(
+        &mut self,
+        kind_token: Token,
+        mut pattern: Box<dyn Pattern>,
+    ) -> Result<DeclareStatement, AllError> {
+        //第一个变量的模式已经由调用者解析，for 语句需要先看到它才能区分 for-in/of
+        let kind = DeclareKind::new(&kind_token.token);
+        let mut declarations = vec![];
+        loop {
+            let declarator = self.variable_declarator(&kind, pattern)?;
+            declarations.push(declarator);
+            if !self.next_n_match(vec![TokenRow::Comma])? {
+                break;
+            }
+            self.advance()?;
+            pattern = self.binding_target()?;
+        }
+        let end = declarations.last().unwrap().end.clone();
+        Ok(DeclareStatement::new(
+            kind,
+            declarations,
+            (kind_token.position, end),
+        ))
+    }
+
+    fn variable_declarator(
+        &mut self,
+        kind: &DeclareKind,
+        pattern: Box<dyn Pattern>,
+    ) -> Result<VariableDeclarator, AllError> {
+        let is_identifier = pattern.is_identifier();
+        let mut value = None;
+        if self.next_n_match(vec![TokenRow::Eq])? {
             self.advance()?;
+            value = Some(self.right_value()?);
+        } else if *kind == DeclareKind::Const {
+            return Err(ParseError {
+                code: 400,
+                describe: "missing initializer in const declaration".to_string(),
+                position: pattern.get_position().0,
+            }
+            .into());
+        } else if !is_identifier {
+            return Err(ParseError {
+                code: 400,
+                describe: "missing initializer in destructuring declaration".to_string(),
+                position: pattern.get_position().0,
+            }
+            .into());
+        }
+        Ok(VariableDeclarator::new(pattern, value))
+    }
+
+    pub fn if_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let if_token = self.advance()?.unwrap();
+        self.expect(if_token.position.clone(), TokenRow::LeftParent)?;
+        self.advance()?;
+        let condition = self.expresson()?;
+        self.expect(if_token.position.clone(), TokenRow::RightParent)?;
+        self.advance()?;
+        let then_branch = self.statement()?;
+        let mut end_position = then_branch.get_position().1;
+        let mut else_branch = None;
+        if self.next_n_match(vec![TokenRow::Else])? {
+            self.advance()?.unwrap();
+            let _else_branch = self.statement()?;
+            end_position = _else_branch.get_position().1;
+            else_branch = Some(_else_branch);
+        }
+        let if_statement = IfStatement::new(
+            condition,
+            then_branch,
+            else_branch,
+            (if_token.position, end_position),
+        );
+        Ok(Box::new(if_statement))
+    }
+
+    pub fn for_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let for_token = self.advance()?.unwrap();
+        let start = for_token.position.clone();
+
+        self.expect(for_token.position, TokenRow::LeftParent)?;
+        let left_parent = self.advance()?.unwrap();
+
+        //先解析第一部分，再根据后面是 ; 还是 in/of 决定是哪一种 for
+        let mut init_statement: Option<Box<dyn StateMent>> = None;
+        if self.next_n_match(vec![TokenRow::Let, TokenRow::Const, TokenRow::Var])? {
+            let kind_token = self.advance()?.unwrap();
+            let pattern = self.binding_target()?;
+            if self.next_is_in_or_of()? {
+                let declarator = VariableDeclarator::new(pattern, None);
+                let end = declarator.end.clone();
+                let kind = DeclareKind::new(&kind_token.token);
+                let declaration =
+                    DeclareStatement::new(kind, vec![declarator], (kind_token.position, end));
+                return self.for_in_of_statement(ForLeft::Declaration(declaration), start);
+            }
+            init_statement = Some(Box::new(self.declarations(kind_token, pattern)?));
+        } else if !self.next_n_is(0, vec![TokenRow::Semicolon])? {
+            let outer = self.cover_initializer.take();
+            let exp = self.assignment_cover()?;
+            if self.next_is_in_or_of()? {
+                self.cover_initializer = outer;
+                let pattern = exp.into_pattern(false).map_err(|error| ParseError {
+                    describe: "invalid left-hand side in for loop".to_string(),
+                    ..error
+                })?;
+                return self.for_in_of_statement(ForLeft::Pattern(pattern), start);
+            }
+            self.check_cover_initializer(outer)?;
+            init_statement = Some(Box::new(ExpressionStatement::new(exp)));
         }
+        let end = match &init_statement {
+            Some(init_statement) => init_statement.get_position().1,
+            None => left_parent.position,
+        };
+        self.expect(end, TokenRow::Semicolon)?;
+        self.advance()?;
 
         let mut condition: Option<Box<dyn Exp>> = None;
         if !self.next_n_is(0, vec![TokenRow::Semicolon])? {
@@ -346,7 +1373,7 @@ impl<'a> Parser {
             self.advance()?;
         }
 
-        let body = self.statement()?;
+        let body = self.loop_body()?;
 
         let end = body.get_position().1;
 
@@ -360,6 +1387,62 @@ impl<'a> Parser {
         Ok(Box::new(for_statement))
     }
 
+    fn next_is_in_or_of(&mut self) -> Result<bool, AllError> {
+        //of 不是关键字，只在 for 的这个位置有特殊含义
+        Ok(match self.peek_n(0)? {
+            Some(Token {
+                token: TokenRow::In,
+                ..
+            }) => true,
+            Some(Token {
+                token: TokenRow::Identifier(name),
+                ..
+            }) => name == "of",
+            _ => false,
+        })
+    }
+
+    fn for_in_of_statement(
+        &mut self,
+        left: ForLeft,
+        start: Position,
+    ) -> Result<Box<dyn StateMent>, AllError> {
+        let token = self.advance()?.unwrap();
+        let is_in = token.token == TokenRow::In;
+        //for-of 的右边是赋值表达式，for-in 的右边可以是逗号表达式
+        let right = if is_in {
+            self.expresson()?
+        } else {
+            self.assignment()?
+        };
+        self.expect(right.get_position().1, TokenRow::RightParent)?;
+        self.advance()?;
+        let body = self.loop_body()?;
+        if is_in {
+            Ok(Box::new(ForInStatement::new(left, right, body, start)))
+        } else {
+            Ok(Box::new(ForOfStatement::new(left, right, body, start)))
+        }
+    }
+
+    pub fn do_while_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let do_token = self.advance()?.unwrap();
+        let body = self.loop_body()?;
+        if body.need_semi() {
+            self.consume_semicolon(body.get_position().1)?;
+        }
+        self.expect(body.get_position().1, TokenRow::While)?;
+        let while_token = self.advance()?.unwrap();
+        self.expect(while_token.position, TokenRow::LeftParent)?;
+        self.advance()?;
+        let condition = self.expresson()?;
+        self.expect(condition.get_position().1, TokenRow::RightParent)?;
+        let right_parent = self.advance()?.unwrap();
+        let do_while_statement =
+            DoWhileStatement::new(body, condition, (do_token.position, right_parent.position));
+        Ok(Box::new(do_while_statement))
+    }
+
     pub fn while_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
         let while_token = self.advance()?.unwrap();
         self.expect(while_token.position.clone(), TokenRow::LeftParent)?;
@@ -367,19 +1450,164 @@ impl<'a> Parser {
         let condition = self.expresson()?;
         self.expect(condition.get_position().1, TokenRow::RightParent)?;
         self.advance()?;
-        let body = self.statement()?;
+        let body = self.loop_body()?;
         let end_position = body.get_position().1;
         let while_statement =
             WhileStatement::new(condition, body, (while_token.position, end_position));
         Ok(Box::new(while_statement))
     }
 
+    fn loop_body(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        self.context.loop_depth += 1;
+        let body = self.statement();
+        self.context.loop_depth -= 1;
+        body
+    }
+
+    fn jump_label(&mut self) -> Result<Option<Token>, AllError> {
+        //break 和 continue 后面可选的标签，换行之后的标识符不是标签
+        match self.peek_n(0)? {
+            Some(Token {
+                token: TokenRow::Identifier(_),
+                line_break: false,
+                ..
+            }) => Ok(self.advance()?),
+            _ => Ok(None),
+        }
+    }
+
+    fn jump_end(keyword: &Token, label: &Option<Token>) -> Position {
+        let token = label.as_ref().unwrap_or(keyword);
+        let len = token.to_string().len();
+        Position::new(token.position.row, token.position.col + len - 1)
+    }
+
+    pub fn break_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let break_token = self.advance()?.unwrap();
+        let label = self.jump_label()?;
+        let describe = match &label {
+            Some(label)
+                if !self
+                    .context
+                    .labels
+                    .iter()
+                    .any(|(l, _)| *l == label.to_string()) =>
+            {
+                Some(format!(r#"undefined label "{}""#, label.to_string()))
+            }
+            None if self.context.loop_depth == 0 && self.context.switch_depth == 0 => {
+                Some("illegal break statement".to_string())
+            }
+            _ => None,
+        };
+        if let Some(describe) = describe {
+            return Err(ParseError {
+                code: 400,
+                describe,
+                position: break_token.position,
+            }
+            .into());
+        }
+        let end = Self::jump_end(&break_token, &label);
+        let break_statement = BreakStatement::new(
+            label.map(|label| label.to_string()),
+            (break_token.position, end),
+        );
+        Ok(Box::new(break_statement))
+    }
+
+    pub fn continue_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let continue_token = self.advance()?.unwrap();
+        let label = self.jump_label()?;
+        let describe = if self.context.loop_depth == 0 {
+            Some("illegal continue statement".to_string())
+        } else {
+            match &label {
+                Some(label)
+                    if !self
+                        .context
+                        .labels
+                        .iter()
+                        .any(|(l, is_loop)| *is_loop && *l == label.to_string()) =>
+                {
+                    Some(format!(
+                        r#"undefined label "{}" or it does not denote a loop"#,
+                        label.to_string()
+                    ))
+                }
+                _ => None,
+            }
+        };
+        if let Some(describe) = describe {
+            return Err(ParseError {
+                code: 400,
+                describe,
+                position: continue_token.position,
+            }
+            .into());
+        }
+        let end = Self::jump_end(&continue_token, &label);
+        let continue_statement = ContinueStatement::new(
+            label.map(|label| label.to_string()),
+            (continue_token.position, end),
+        );
+        Ok(Box::new(continue_statement))
+    }
+
+    pub fn labeled_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        //连续的标签 a: b: for (...) 都指向同一个语句
+        let mut labels = vec![];
+        while matches!(
+            self.peek_n(0)?,
+            Some(Token {
+                token: TokenRow::Identifier(_),
+                ..
+            })
+        ) && self.next_n_is(1, vec![TokenRow::Colon])?
+        {
+            let label = self.advance()?.unwrap();
+            self.advance()?;
+            let name = label.to_string();
+            if self.context.labels.iter().any(|(l, _)| *l == name)
+                || labels.iter().any(|l: &Token| l.to_string() == name)
+            {
+                return Err(ParseError {
+                    code: 400,
+                    describe: format!(r#"label "{}" has already been declared"#, name),
+                    position: label.position,
+                }
+                .into());
+            }
+            labels.push(label);
+        }
+        let is_loop = self.next_n_match(vec![TokenRow::For, TokenRow::While, TokenRow::Do])?;
+        let outer_len = self.context.labels.len();
+        for label in labels.iter() {
+            self.context.labels.push((label.to_string(), is_loop));
+        }
+        let body = self.statement();
+        self.context.labels.truncate(outer_len);
+        let mut statement = body?;
+        for label in labels.into_iter().rev() {
+            statement = Box::new(LabeledStatement::new(
+                label.to_string(),
+                statement,
+                label.position,
+            ));
+        }
+        Ok(statement)
+    }
+
     pub fn return_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
         let return_token = self.advance()?.unwrap();
         let mut exp: Option<Box<dyn Exp>> = None;
         let start = return_token.position;
         let mut end = Position::new(start.row, start.col + 6);
-        if !self.next_n_is(0, vec![TokenRow::Semicolon])? {
+        //return 后面换行时自动插入分号，返回值为空
+        if !self.is_end()?
+            && !self.line_break_before(0)?
+            && !self.next_n_match(vec![TokenRow::Semicolon, TokenRow::RightBrace])?
+        {
             let _exp = self.expresson()?;
             end = _exp.get_position().1;
             exp = Some(_exp);
@@ -388,6 +1616,144 @@ impl<'a> Parser {
         Ok(Box::new(return_statement))
     }
 
+    pub fn switch_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let switch_token = self.advance()?.unwrap();
+        self.expect(switch_token.position.clone(), TokenRow::LeftParent)?;
+        self.advance()?;
+        let discriminant = self.expresson()?;
+        self.expect(discriminant.get_position().1, TokenRow::RightParent)?;
+        let right_parent = self.advance()?.unwrap();
+        self.expect(right_parent.position, TokenRow::LeftBrace)?;
+        self.advance()?;
+        self.context.switch_depth += 1;
+        let cases = self.switch_cases();
+        self.context.switch_depth -= 1;
+        let cases = cases?;
+        let right_brace = self.advance()?.unwrap();
+        let switch_statement = SwitchStatement::new(
+            discriminant,
+            cases,
+            (switch_token.position, right_brace.position),
+        );
+        Ok(Box::new(switch_statement))
+    }
+
+    fn switch_cases(&mut self) -> Result<Vec<SwitchCase>, AllError> {
+        let mut cases = vec![];
+        let mut has_default = false;
+        while !self.next_n_match(vec![TokenRow::RightBrace])? {
+            let case = self.switch_case()?;
+            if case.test.is_none() {
+                if has_default {
+                    return Err(ParseError {
+                        code: 400,
+                        describe: "more than one default clause in switch statement".to_string(),
+                        position: case.start,
+                    }
+                    .into());
+                }
+                has_default = true;
+            }
+            cases.push(case);
+        }
+        Ok(cases)
+    }
+
+    fn switch_case(&mut self) -> Result<SwitchCase, AllError> {
+        let token = match self.advance()? {
+            Some(token) => token,
+            None => return Err(NoContentError::new().into()),
+        };
+        let test = match token.token {
+            TokenRow::Case => Some(self.expresson()?),
+            TokenRow::Default => None,
+            _ => return Err(ParseError::from(token).into()),
+        };
+        let end = match &test {
+            Some(test) => test.get_position().1,
+            None => Position::new(token.position.row, token.position.col + 6),
+        };
+        self.expect(end, TokenRow::Colon)?;
+        let colon = self.advance()?.unwrap();
+        let mut consequent = vec![];
+        while !self.next_n_match(vec![
+            TokenRow::Case,
+            TokenRow::Default,
+            TokenRow::RightBrace,
+        ])? {
+            if self.next_n_match(vec![TokenRow::Semicolon])? {
+                self.advance()?;
+                continue;
+            }
+            if self.is_end()? {
+                return Err(NoContentError::new().into());
+            }
+            consequent.push(self.statement_item()?);
+        }
+        Ok(SwitchCase::new(
+            test,
+            consequent,
+            (token.position, colon.position),
+        ))
+    }
+
+    pub fn throw_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let throw_token = self.advance()?.unwrap();
+        if self.line_break_before(0)? {
+            return Err(ParseError {
+                code: 400,
+                describe: "illegal newline after throw".to_string(),
+                position: throw_token.position,
+            }
+            .into());
+        }
+        let argument = self.expresson()?;
+        let throw_statement = ThrowStatement::new(argument, throw_token.position);
+        Ok(Box::new(throw_statement))
+    }
+
+    pub fn try_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let try_token = self.advance()?.unwrap();
+        self.expect(try_token.position.clone(), TokenRow::LeftBrace)?;
+        let block = self.block_row()?;
+        let mut handler = None;
+        if self.next_n_match(vec![TokenRow::Catch])? {
+            let catch_token = self.advance()?.unwrap();
+            let mut position = catch_token.position.clone();
+            let mut param = None;
+            if self.next_n_match(vec![TokenRow::LeftParent])? {
+                self.advance()?;
+                let pattern = self.binding_target()?;
+                position = pattern.get_position().1;
+                self.expect(position.clone(), TokenRow::RightParent)?;
+                self.advance()?;
+                param = Some(pattern);
+            }
+            self.expect(position, TokenRow::LeftBrace)?;
+            let body = self.block_row()?;
+            handler = Some(CatchClause::new(param, body, catch_token.position));
+        }
+        let mut finalizer = None;
+        if self.next_n_match(vec![TokenRow::Finally])? {
+            let finally_token = self.advance()?.unwrap();
+            self.expect(finally_token.position, TokenRow::LeftBrace)?;
+            finalizer = Some(self.block_row()?);
+        }
+        if handler.is_none() && finalizer.is_none() {
+            return Err(ParseError {
+                code: 400,
+                describe: "missing catch or finally after try".to_string(),
+                position: Position {
+                    row: block.end.row,
+                    col: block.end.col + 1,
+                },
+            }
+            .into());
+        }
+        let try_statement = TryStatement::new(block, handler, finalizer, try_token.position);
+        Ok(Box::new(try_statement))
+    }
+
     pub fn expression_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
         let exp = self.expresson()?;
         let statement = ExpressionStatement::new(exp);
@@ -395,6 +1761,11 @@ impl<'a> Parser {
     }
 
     pub fn block(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let block = self.block_row()?;
+        Ok(Box::new(block))
+    }
+
+    fn block_row(&mut self) -> Result<Block, AllError> {
         let left_brace = self.advance()?.unwrap();
         let mut body = vec![];
         while !self.next_n_match(vec![TokenRow::RightBrace])? {
@@ -402,12 +1773,7 @@ impl<'a> Parser {
                 self.advance()?;
             }
             if !self.next_n_match(vec![TokenRow::RightBrace])? {
-                let statement = self.statement()?;
-                if statement.need_semi() {
-                    self.expect(statement.get_position().1, TokenRow::Semicolon)?;
-                    self.advance()?;
-                }
-                body.push(statement);
+                body.push(self.statement_item()?);
             }
 
             while !self.is_end()? && self.next_n_is(0, vec![TokenRow::Semicolon])? {
@@ -415,134 +1781,811 @@ impl<'a> Parser {
             }
         }
         let right_brace = self.advance()?.unwrap();
-        let block = Block::new(body, (left_brace.position, right_brace.position));
-        Ok(Box::new(block))
+        Ok(Block::new(
+            body,
+            (left_brace.position, right_brace.position),
+        ))
     }
 
     pub fn get_args(&mut self) -> Result<Arguments, AllError> {
         let mut args = vec![];
-        let mut position = None;
         while !self.next_n_match(vec![TokenRow::RightParent])? {
-            let arg = self.advance()?.unwrap();
-            if !matches!(arg.token, TokenRow::Identifier(_) | TokenRow::Digital(_)) {
-                return Err(ParseError::from(arg.position).into());
+            if self.next_n_match(vec![TokenRow::Ellipsis])? {
+                let param = self.rest_param()?;
+                self.expect(param.end.clone(), TokenRow::RightParent)?;
+                args.push(param);
+                break;
             }
-            args.push(arg);
-            match self.peek_n(0)? {
-                Some(&Token {
-                    token: TokenRow::Comma,
-                    ..
-                }) => {
-                    let comma = self.advance()?.unwrap();
-                    if !matches!(
-                        self.peek_n(0)?.unwrap(),
-                        &Token {
-                            token: TokenRow::Identifier(_),
-                            ..
-                        } | &Token {
-                            token: TokenRow::Digital(_),
-                            ..
-                        }
-                    ) {
-                        return Err(ParseError::from(comma.position).into());
-                    }
-                }
-                _ => {}
+            let element = self.binding_element()?;
+            let start = element.start.clone();
+            let param = Param::new(element, false, start);
+            let end = param.end.clone();
+            args.push(param);
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+            } else {
+                self.expect(end, TokenRow::RightParent)?;
             }
         }
-        if let (Some(start), Some(end)) = (args.first(), args.last()) {
-            position = Some((start.position.clone(), end.position.clone()))
+        Ok(Arguments::new(args))
+    }
+
+    fn rest_param(&mut self) -> Result<Param, AllError> {
+        let ellipsis = self.advance()?.unwrap();
+        let target = self.binding_target()?;
+        Ok(Param::new(
+            PatternElement::new(target, None),
+            true,
+            ellipsis.position,
+        ))
+    }
+
+    fn binding_element(&mut self) -> Result<PatternElement, AllError> {
+        //解构的一项：a、{a}、[a]，后面可以跟默认值 = 1
+        let target = self.binding_target()?;
+        let mut default = None;
+        if self.next_n_match(vec![TokenRow::Eq])? {
+            self.advance()?;
+            default = Some(self.assignment()?);
         }
-        let args = args.into_iter().map(|item| item.to_string()).collect();
-        let args = Arguments::new(args, position);
-        Ok(args)
+        Ok(PatternElement::new(target, default))
+    }
+
+    fn binding_target(&mut self) -> Result<Box<dyn Pattern>, AllError> {
+        match self.peek_n(0)?.cloned() {
+            Some(
+                token @ Token {
+                    token: TokenRow::Identifier(_),
+                    ..
+                },
+            ) => {
+                self.check_reserved(&token)?;
+                self.advance()?;
+                let Token {
+                    token, position, ..
+                } = token;
+                let name = token.to_string();
+                Ok(IdentifierPattern::new(name, position))
+            }
+            Some(Token {
+                token: TokenRow::LeftBracket,
+                ..
+            }) => self.array_pattern(),
+            Some(Token {
+                token: TokenRow::LeftBrace,
+                ..
+            }) => self.object_pattern(),
+            Some(token) => Err(ParseError::from(token).into()),
+            None => Err(NoContentError::new().into()),
+        }
+    }
+
+    fn array_pattern(&mut self) -> Result<Box<dyn Pattern>, AllError> {
+        let left_bracket = self.advance()?.unwrap();
+        let mut elements = vec![];
+        let mut rest = None;
+        while !self.next_n_match(vec![TokenRow::RightBracket])? {
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+                elements.push(None);
+                continue;
+            }
+            if self.next_n_match(vec![TokenRow::Ellipsis])? {
+                self.advance()?;
+                let target = self.binding_target()?;
+                self.expect(target.get_position().1, TokenRow::RightBracket)?;
+                rest = Some(target);
+                break;
+            }
+            let element = self.binding_element()?;
+            let end = element.end.clone();
+            elements.push(Some(element));
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+            } else {
+                self.expect(end, TokenRow::RightBracket)?;
+            }
+        }
+        let right_bracket = self.advance()?.unwrap();
+        Ok(ArrayPattern::new(
+            elements,
+            rest,
+            (left_bracket.position, right_bracket.position),
+        ))
+    }
+
+    fn object_pattern(&mut self) -> Result<Box<dyn Pattern>, AllError> {
+        let left_brace = self.advance()?.unwrap();
+        let mut properties = vec![];
+        let mut rest = None;
+        while !self.next_n_match(vec![TokenRow::RightBrace])? {
+            if self.next_n_match(vec![TokenRow::Ellipsis])? {
+                //对象的剩余属性只能是标识符
+                self.advance()?;
+                let target = match self.peek_n(0)? {
+                    Some(Token {
+                        token: TokenRow::Identifier(_),
+                        ..
+                    }) => self.binding_target()?,
+                    Some(token) => return Err(ParseError::from(token.clone()).into()),
+                    None => return Err(NoContentError::new().into()),
+                };
+                self.expect(target.get_position().1, TokenRow::RightBrace)?;
+                rest = Some(target);
+                break;
+            }
+            let property = self.pattern_property()?;
+            let end = property.end.clone();
+            properties.push(property);
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+            } else {
+                self.expect(end, TokenRow::RightBrace)?;
+            }
+        }
+        let right_brace = self.advance()?.unwrap();
+        Ok(ObjectPattern::new(
+            properties,
+            rest,
+            (left_brace.position, right_brace.position),
+        ))
+    }
+
+    fn pattern_property(&mut self) -> Result<PatternProperty, AllError> {
+        if let Some(Token {
+            token: TokenRow::Identifier(name),
+            position,
+            ..
+        }) = self.peek_n(0)?.cloned()
+        {
+            //简写 {a} 或者 {a = 1}
+            if !self.next_n_is(1, vec![TokenRow::Colon])? {
+                let key = PrimaryExpression::new(PrimaryRow::Identifier(name), position.clone());
+                let value = self.binding_element()?;
+                return Ok(PatternProperty::new(key, value, false, true, position));
+            }
+        }
+        let (key, computed, start) = self.property_key()?;
+        self.expect(key.get_position().1, TokenRow::Colon)?;
+        self.advance()?;
+        let value = self.binding_element()?;
+        Ok(PatternProperty::new(key, value, computed, false, start))
     }
 
     pub fn function_declare_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        let function_token = self.advance()?.unwrap();
+        let start = self.peek_n(0)?.unwrap().position.clone();
+        let flags = self.function_flags()?;
         let identity = self.advance()?;
         match identity {
             Some(Token {
                 token: TokenRow::Identifier(name),
                 position,
+                ..
             }) => {
-                self.expect(position, TokenRow::LeftParent)?;
-                dbg!(self.advance()?);
-                let args = self.get_args()?;
-                self.advance()?;
-                let _body = self.block()?;
-                let body = std::mem::take(unsafe { &mut *(Box::into_raw(_body) as *mut Block) });
+                let args = self.function_params(position)?;
+                let body = self.function_body(flags)?;
                 let end = body.get_position().1;
                 let function_declare_statement =
-                    FunctionDeclareStatement::new(name, args, body, (function_token.position, end));
+                    FunctionDeclareStatement::new(name, args, body, flags, (start, end));
                 Ok(Box::new(function_declare_statement))
             }
-            _ => Err(AllError::ParseError(ParseError {
+            Some(token) => Err(AllError::ParseError(ParseError {
                 code: 400,
                 describe: "function name is expected".to_string(),
+                position: token.position,
+            })),
+            None => Err(NoContentError::new().into()),
+        }
+    }
+
+    fn function_params(&mut self, position: Position) -> Result<Arguments, AllError> {
+        self.expect(position, TokenRow::LeftParent)?;
+        let left_parent = self.advance()?.unwrap();
+        let args = self.get_args()?;
+        self.expect(left_parent.position, TokenRow::RightParent)?;
+        self.advance()?;
+        Ok(args)
+    }
+
+    fn function_body(&mut self, flags: FunctionFlags) -> Result<Block, AllError> {
+        //函数体有自己的标签和循环上下文
+        self.body_with(Context {
+            in_async: flags.is_async,
+            in_generator: flags.is_generator,
+            ..Default::default()
+        })
+    }
+
+    fn body_with(&mut self, context: Context) -> Result<Block, AllError> {
+        let outer = std::mem::replace(&mut self.context, context);
+        let body = self.block_row();
+        self.context = outer;
+        body
+    }
+
+    pub fn class_declaration(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        let class_token = self.advance()?.unwrap();
+        match self.advance()? {
+            Some(Token {
+                token: TokenRow::Identifier(name),
+                position,
+                ..
+            }) => {
+                let (super_class, body) = self.class_tail(position)?;
+                let class_declaration =
+                    ClassDeclaration::new(name, super_class, body, class_token.position);
+                Ok(Box::new(class_declaration))
+            }
+            _ => Err(ParseError {
+                code: 400,
+                describe: "class name is expected".to_string(),
                 position: Position {
-                    row: function_token.position.row,
-                    col: function_token.position.col + 1,
+                    row: class_token.position.row,
+                    col: class_token.position.col + 1,
                 },
-            })),
+            }
+            .into()),
         }
     }
 
-    pub fn get_arguments(&mut self) -> Result<Arguments, AllError> {
-        let mut args = vec![];
-        while !self.next_n_match(vec![TokenRow::RightParent])? {
-            let arg = self.advance()?.unwrap();
-            if !matches!(arg.token, TokenRow::Identifier(_) | TokenRow::Digital(_)) {
-                return Err(ParseError::from(arg.position).into());
+    pub fn class_expression(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        let class_token = self.advance()?.unwrap();
+        let mut name = None;
+        let mut position = class_token.position.clone();
+        if let Some(Token {
+            token: TokenRow::Identifier(_),
+            ..
+        }) = self.peek_n(0)?
+        {
+            let name_token = self.advance()?.unwrap();
+            position = name_token.position.clone();
+            name = Some(name_token.to_string());
+        }
+        let (super_class, body) = self.class_tail(position)?;
+        Ok(ClassExpression::new(
+            name,
+            super_class,
+            body,
+            class_token.position,
+        ))
+    }
+
+    fn class_tail(
+        &mut self,
+        mut position: Position,
+    ) -> Result<(Option<Box<dyn Exp>>, ClassBody), AllError> {
+        let mut super_class = None;
+        if self.next_n_match(vec![TokenRow::Extends])? {
+            self.advance()?;
+            let exp = self.call()?;
+            position = exp.get_position().1;
+            super_class = Some(exp);
+        }
+        self.expect(position, TokenRow::LeftBrace)?;
+        let left_brace = self.advance()?.unwrap();
+        let mut members = vec![];
+        let mut has_constructor = false;
+        while !self.next_n_match(vec![TokenRow::RightBrace])? {
+            if self.next_n_match(vec![TokenRow::Semicolon])? {
+                self.advance()?;
+                continue;
             }
-            args.push(arg);
-            match self.peek_n(0)? {
-                Some(&Token {
-                    token: TokenRow::Comma,
+            let member = self.class_member(super_class.is_some())?;
+            if let ClassMember::Method(MethodDefinition {
+                kind: MethodKind::Constructor,
+                start,
+                ..
+            }) = &member
+            {
+                if has_constructor {
+                    return Err(ParseError {
+                        code: 400,
+                        describe: "a class may only have one constructor".to_string(),
+                        position: start.clone(),
+                    }
+                    .into());
+                }
+                has_constructor = true;
+            }
+            members.push(member);
+        }
+        let right_brace = self.advance()?.unwrap();
+        let body = ClassBody::new(members, (left_brace.position, right_brace.position));
+        Ok((super_class, body))
+    }
+
+    fn class_member(&mut self, derived: bool) -> Result<ClassMember, AllError> {
+        let start = match self.peek_n(0)? {
+            Some(token) => token.position.clone(),
+            None => return Err(NoContentError::new().into()),
+        };
+        //static、get、set 后面紧跟 ( = ; } 时是普通的成员名，如 static() {}
+        let name_end = vec![
+            TokenRow::LeftParent,
+            TokenRow::Eq,
+            TokenRow::Semicolon,
+            TokenRow::RightBrace,
+        ];
+        let mut is_static = false;
+        if self.next_n_match(vec![TokenRow::Static])? && !self.next_n_is(1, name_end.clone())? {
+            self.advance()?;
+            is_static = true;
+        }
+        let (flags, mut kind) = self.method_prefix(name_end)?;
+        //constructor 和 "constructor" 都是构造函数，["constructor"] 不是
+        let is_constructor = !is_static
+            && match self.peek_n(0)? {
+                Some(Token {
+                    token: TokenRow::Identifier(name),
                     ..
-                }) => {
-                    let comma = self.advance()?.unwrap();
-                    if !matches!(
-                        self.peek_n(0)?.unwrap(),
-                        &Token {
-                            token: TokenRow::Identifier(_),
-                            ..
-                        }
-                    ) {
-                        return Err(ParseError::from(comma.position).into());
+                }) => name == "constructor",
+                Some(Token {
+                    token: TokenRow::String { value, .. },
+                    ..
+                }) => value == "constructor",
+                _ => false,
+            };
+        let (key, computed, key_start) = self.property_key()?;
+        if self.next_n_match(vec![TokenRow::LeftParent])? {
+            if is_constructor {
+                let describe = if kind != MethodKind::Method {
+                    Some("class constructor may not be an accessor")
+                } else if flags.is_async {
+                    Some("class constructor may not be an async method")
+                } else if flags.is_generator {
+                    Some("class constructor may not be a generator")
+                } else {
+                    None
+                };
+                if let Some(describe) = describe {
+                    return Err(ParseError {
+                        code: 400,
+                        describe: describe.to_string(),
+                        position: key_start,
                     }
+                    .into());
                 }
-                _ => {}
+                kind = MethodKind::Constructor;
             }
+            let super_call = derived && kind == MethodKind::Constructor;
+            let key = (key, computed, key_start);
+            let mut method = self.method_definition(key, kind, flags, super_call, start)?;
+            method.is_static = is_static;
+            return Ok(ClassMember::Method(method));
         }
-        self.advance()?;
-        // Ok(args);
-        todo!()
+        if kind != MethodKind::Method || flags.is_async || flags.is_generator {
+            self.expect(key.get_position().1, TokenRow::LeftParent)?;
+        }
+        if is_constructor {
+            return Err(ParseError {
+                code: 400,
+                describe: "classes may not have a field named 'constructor'".to_string(),
+                position: key_start,
+            }
+            .into());
+        }
+        //字段的初始值中 this 指向实例，也可以使用 super.a
+        let mut value = None;
+        if self.next_n_match(vec![TokenRow::Eq])? {
+            self.advance()?;
+            let context = Context {
+                super_property: true,
+                ..Default::default()
+            };
+            let outer = std::mem::replace(&mut self.context, context);
+            let exp = self.assignment();
+            self.context = outer;
+            value = Some(exp?);
+        }
+        let field = FieldDefinition::new(key, computed, is_static, value, start);
+        self.consume_semicolon(field.end.clone())?;
+        Ok(ClassMember::Field(field))
+    }
+
+    fn method_prefix(
+        &mut self,
+        name_end: Vec<TokenRow>,
+    ) -> Result<(FunctionFlags, MethodKind), AllError> {
+        //方法名前的 async、*、get、set，后面紧跟 name_end 中的符号时它们是方法名或属性名
+        let mut flags = FunctionFlags::default();
+        if self.next_n_match(vec![TokenRow::Identifier("async".to_string())])?
+            && !self.next_n_is(1, name_end.clone())?
+            && !self.line_break_before(1)?
+        {
+            self.advance()?;
+            flags.is_async = true;
+        }
+        if self.next_n_match(vec![TokenRow::Start])? {
+            self.advance()?;
+            flags.is_generator = true;
+        }
+        let mut kind = MethodKind::Method;
+        if let Some(Token {
+            token: TokenRow::Identifier(name),
+            ..
+        }) = self.peek_n(0)?.cloned()
+        {
+            if (name == "get" || name == "set")
+                && !flags.is_async
+                && !flags.is_generator
+                && !self.next_n_is(1, name_end)?
+            {
+                kind = if name == "get" {
+                    MethodKind::Get
+                } else {
+                    MethodKind::Set
+                };
+                self.advance()?;
+            }
+        }
+        Ok((flags, kind))
+    }
+
+    fn method_definition(
+        &mut self,
+        key: (Box<dyn Exp>, bool, Position),
+        kind: MethodKind,
+        flags: FunctionFlags,
+        super_call: bool,
+        start: Position,
+    ) -> Result<MethodDefinition, AllError> {
+        //类和对象字面量的方法：(参数) { 函数体 }，方法中可以使用 super.a
+        let (key, computed, key_start) = key;
+        let args = self.function_params(key.get_position().1)?;
+        let describe = match kind {
+            MethodKind::Get if !args.args.is_empty() => Some("getter must not have parameters"),
+            MethodKind::Set if args.args.len() != 1 || args.args[0].rest => {
+                Some("setter must have exactly one parameter")
+            }
+            _ => None,
+        };
+        if let Some(describe) = describe {
+            return Err(ParseError {
+                code: 400,
+                describe: describe.to_string(),
+                position: key_start,
+            }
+            .into());
+        }
+        let context = Context {
+            super_property: true,
+            super_call,
+            in_async: flags.is_async,
+            in_generator: flags.is_generator,
+            ..Default::default()
+        };
+        let body = self.body_with(context)?;
+        Ok(MethodDefinition::new(
+            key, computed, kind, flags, args, body, start,
+        ))
+    }
+
+    pub fn get_arguments(&mut self) -> Result<Vec<Box<dyn Exp>>, AllError> {
+        let mut args = vec![];
+        while !self.next_n_match(vec![TokenRow::RightParent])? {
+            let arg = self.spread_or_expression()?;
+            let end = arg.get_position().1;
+            args.push(arg);
+            if self.next_n_match(vec![TokenRow::Comma])? {
+                self.advance()?;
+            } else {
+                self.expect(end, TokenRow::RightParent)?;
+            }
+        }
+        Ok(args)
+    }
+
+    fn statement_item(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        //语句以及它后面的分号
+        let statement = self.statement()?;
+        if statement.need_semi() {
+            self.consume_semicolon(statement.get_position().1)?;
+        }
+        Ok(statement)
+    }
+
+    fn consume_semicolon(&mut self, position: Position) -> Result<(), AllError> {
+        //自动插入分号：下一个 token 是 }、输入已经结束或者和上一个 token 之间有换行时，分号可以省略
+        if self.next_n_match(vec![TokenRow::Semicolon])? {
+            self.advance()?;
+            return Ok(());
+        }
+        if self.is_end()?
+            || self.line_break_before(0)?
+            || self.next_n_match(vec![TokenRow::RightBrace])?
+        {
+            return Ok(());
+        }
+        self.expect(position, TokenRow::Semicolon)?;
+        Ok(())
+    }
+
+    fn line_break_before(&mut self, n: usize) -> Result<bool, AllError> {
+        Ok(self.peek_n(n)?.is_some_and(|token| token.line_break))
+    }
+
+    fn next_is_arrow(&mut self, n: usize) -> Result<bool, AllError> {
+        //=> 之前不能换行
+        Ok(self.next_n_is(n, vec![TokenRow::Arrow])? && !self.line_break_before(n)?)
+    }
+
+    fn module_item(&mut self) -> Result<Box<dyn StateMent>, AllError> {
+        if self.source_type == SourceType::Module {
+            let statement: Option<Box<dyn StateMent>> =
+                if self.next_n_match(vec![TokenRow::Import])? {
+                    Some(Box::new(self.import_declaration()?))
+                } else if self.next_n_match(vec![TokenRow::Export])? {
+                    Some(Box::new(self.export_declaration()?))
+                } else {
+                    None
+                };
+            if let Some(statement) = statement {
+                if statement.need_semi() {
+                    self.consume_semicolon(statement.get_position().1)?;
+                }
+                return Ok(statement);
+            }
+        }
+        self.statement_item()
+    }
+
+    fn import_declaration(&mut self) -> Result<ImportDeclaration, AllError> {
+        let import_token = self.advance()?.unwrap();
+        let mut specifiers = vec![];
+        //import "m" 只执行模块，不导入任何绑定
+        if let Some(Token {
+            token: TokenRow::String { .. },
+            ..
+        }) = self.peek_n(0)?
+        {
+            let source = self.module_specifier()?;
+            return Ok(ImportDeclaration::new(
+                specifiers,
+                source,
+                import_token.position,
+            ));
+        }
+        let mut position = import_token.position.clone();
+        if let Some(Token {
+            token: TokenRow::Identifier(_),
+            ..
+        }) = self.peek_n(0)?
+        {
+            let local = self.binding_identifier()?;
+            position = local.position.clone();
+            specifiers.push(ImportSpecifier::Default(local.to_string()));
+            if !self.next_n_match(vec![TokenRow::Comma])? {
+                let source = self.module_source(position)?;
+                return Ok(ImportDeclaration::new(
+                    specifiers,
+                    source,
+                    import_token.position,
+                ));
+            }
+            position = self.advance()?.unwrap().position;
+        }
+        if self.next_n_match(vec![TokenRow::Start])? {
+            self.advance()?;
+            self.expect(position, TokenRow::Identifier("as".to_string()))?;
+            self.advance()?;
+            let local = self.binding_identifier()?;
+            position = local.position.clone();
+            specifiers.push(ImportSpecifier::Namespace(local.to_string()));
+        } else if self.next_n_match(vec![TokenRow::LeftBrace])? {
+            self.advance()?;
+            while !self.next_n_match(vec![TokenRow::RightBrace])? {
+                let imported = self.module_export_name()?;
+                let mut local = imported.clone();
+                if self.next_n_match(vec![TokenRow::Identifier("as".to_string())])? {
+                    self.advance()?;
+                    local = self.binding_identifier()?;
+                } else if !matches!(imported.token, TokenRow::Identifier(_)) {
+                    //import { default } from "m" 缺少 as
+                    return Err(ParseError::from(imported).into());
+                } else {
+                    self.check_reserved(&local)?;
+                }
+                specifiers.push(ImportSpecifier::Named {
+                    imported: imported.to_string(),
+                    local: local.to_string(),
+                });
+                if self.next_n_match(vec![TokenRow::Comma])? {
+                    self.advance()?;
+                } else {
+                    self.expect(local.position, TokenRow::RightBrace)?;
+                }
+            }
+            position = self.advance()?.unwrap().position;
+        } else {
+            return match self.advance()? {
+                Some(token) => Err(ParseError::from(token).into()),
+                None => Err(NoContentError::new().into()),
+            };
+        }
+        let source = self.module_source(position)?;
+        Ok(ImportDeclaration::new(
+            specifiers,
+            source,
+            import_token.position,
+        ))
     }
 
-    pub fn function_call_statement(&mut self) -> Result<Box<dyn StateMent>, AllError> {
-        let function_name = self.advance()?;
-        let temp = function_name.clone().unwrap();
-        match function_name {
+    fn export_declaration(&mut self) -> Result<ExportDeclaration, AllError> {
+        let export_token = self.advance()?.unwrap();
+        let start = export_token.position.clone();
+        match self.peek_n(0)?.cloned() {
             Some(Token {
-                token: TokenRow::Identifier(name),
+                token: TokenRow::Start,
                 position,
+                ..
             }) => {
                 self.advance()?;
-                let args = self.get_args()?;
-                let rights = self.advance()?.unwrap();
-                let function_call_statement =
-                    FunctionCall::new(name, args, (position, rights.position));
-                Ok(Box::new(function_call_statement))
+                let mut exported = None;
+                let mut position = position;
+                if self.next_n_match(vec![TokenRow::Identifier("as".to_string())])? {
+                    self.advance()?;
+                    let name = self.module_export_name()?;
+                    position = name.position.clone();
+                    exported = Some(name.to_string());
+                }
+                let source = self.module_source(position)?;
+                let end = source.get_position().1;
+                Ok(ExportDeclaration::new(
+                    ExportKind::All { exported, source },
+                    (start, end),
+                ))
             }
-            _ => Err(AllError::ParseError(ParseError {
+            Some(Token {
+                token: TokenRow::LeftBrace,
+                ..
+            }) => {
+                self.advance()?;
+                let mut specifiers = vec![];
+                let mut keywords = vec![];
+                while !self.next_n_match(vec![TokenRow::RightBrace])? {
+                    let local = self.module_export_name()?;
+                    let mut exported = local.clone();
+                    if self.next_n_match(vec![TokenRow::Identifier("as".to_string())])? {
+                        self.advance()?;
+                        exported = self.module_export_name()?;
+                    }
+                    if !matches!(local.token, TokenRow::Identifier(_)) {
+                        keywords.push(local.clone());
+                    }
+                    specifiers.push(ExportSpecifier {
+                        local: local.to_string(),
+                        exported: exported.to_string(),
+                    });
+                    if self.next_n_match(vec![TokenRow::Comma])? {
+                        self.advance()?;
+                    } else {
+                        self.expect(exported.position, TokenRow::RightBrace)?;
+                    }
+                }
+                let right_brace = self.advance()?.unwrap();
+                let mut end = right_brace.position.clone();
+                let mut source = None;
+                if self.next_n_match(vec![TokenRow::Identifier("from".to_string())])? {
+                    let exp = self.module_source(right_brace.position)?;
+                    end = exp.get_position().1;
+                    source = Some(exp);
+                } else if let Some(keyword) = keywords.pop() {
+                    //没有 from 时导出的是本模块的绑定，不能是关键字
+                    return Err(ParseError::from(keyword).into());
+                }
+                Ok(ExportDeclaration::new(
+                    ExportKind::Named { specifiers, source },
+                    (start, end),
+                ))
+            }
+            Some(Token {
+                token: TokenRow::Default,
+                ..
+            }) => {
+                self.advance()?;
+                let is_class = self.next_n_match(vec![TokenRow::Class])?;
+                let is_function = self.next_n_match(vec![TokenRow::Function])?
+                    || self.next_is_async_function()?;
+                let is_expression = !is_class && !is_function;
+                //export default function () {} 是声明，到函数体结束为止，后面的 (1) 是另一条语句
+                let value = if is_class {
+                    self.class_expression()?
+                } else if is_function {
+                    self.function_expression()?
+                } else {
+                    self.assignment()?
+                };
+                let end = value.get_position().1;
+                Ok(ExportDeclaration::new(
+                    ExportKind::Default {
+                        value,
+                        is_expression,
+                    },
+                    (start, end),
+                ))
+            }
+            Some(Token {
+                token:
+                    TokenRow::Let
+                    | TokenRow::Const
+                    | TokenRow::Var
+                    | TokenRow::Function
+                    | TokenRow::Class,
+                ..
+            }) => {
+                let declaration = self.statement()?;
+                let end = declaration.get_position().1;
+                Ok(ExportDeclaration::new(
+                    ExportKind::Declaration(declaration),
+                    (start, end),
+                ))
+            }
+            Some(Token {
+                token: TokenRow::Identifier(_),
+                ..
+            }) if self.next_is_async_function()? => {
+                let declaration = self.statement()?;
+                let end = declaration.get_position().1;
+                Ok(ExportDeclaration::new(
+                    ExportKind::Declaration(declaration),
+                    (start, end),
+                ))
+            }
+            Some(token) => Err(ParseError::from(token).into()),
+            None => Err(NoContentError::new().into()),
+        }
+    }
+
+    fn module_source(&mut self, position: Position) -> Result<Box<dyn Exp>, AllError> {
+        //from "m"
+        self.expect(position, TokenRow::Identifier("from".to_string()))?;
+        self.advance()?;
+        self.module_specifier()
+    }
+
+    fn module_specifier(&mut self) -> Result<Box<dyn Exp>, AllError> {
+        match self.advance()? {
+            Some(Token {
+                token: TokenRow::String { value, raw },
+                position,
+                ..
+            }) => Ok(PrimaryExpression::new(
+                PrimaryRow::String { value, raw },
+                position,
+            )),
+            Some(token) => Err(ParseError {
                 code: 400,
-                describe: "function name is expected".to_string(),
-                position: Position {
-                    row: temp.position.row,
-                    col: temp.position.col + 1,
+                describe: "module specifier is expected".to_string(),
+                position: token.position,
+            }
+            .into()),
+            None => Err(NoContentError::new().into()),
+        }
+    }
+
+    fn module_export_name(&mut self) -> Result<Token, AllError> {
+        //导入导出的名字可以是关键字，如 export { a as default }
+        match self.advance()? {
+            Some(token) if token.is_identifier_name() => Ok(token),
+            Some(token) => Err(ParseError::from(token).into()),
+            None => Err(NoContentError::new().into()),
+        }
+    }
+
+    fn binding_identifier(&mut self) -> Result<Token, AllError> {
+        //导入的绑定和 let 一样，模块中不能叫 await
+        match self.advance()? {
+            Some(
+                token @ Token {
+                    token: TokenRow::Identifier(_),
+                    ..
                 },
-            })),
+            ) => {
+                self.check_reserved(&token)?;
+                Ok(token)
+            }
+            Some(token) => Err(ParseError::from(token).into()),
+            None => Err(NoContentError::new().into()),
         }
     }
 
@@ -552,12 +2595,7 @@ impl<'a> Parser {
             while !self.is_end()? && self.next_n_is(0, vec![TokenRow::Semicolon])? {
                 self.advance()?;
             }
-            let statement = self.statement()?;
-            if statement.need_semi() {
-                self.expect(statement.get_position().1, TokenRow::Semicolon)?;
-                self.advance()?;
-            }
-            programing.push(statement);
+            programing.push(self.module_item()?);
             while !self.is_end()? && self.next_n_is(0, vec![TokenRow::Semicolon])? {
                 self.advance()?;
             }
@@ -565,3 +2603,583 @@ impl<'a> Parser {
         Ok(programing)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Result<Vec<Box<dyn StateMent>>, AllError> {
+        Parser::new(source).programing()
+    }
+
+    fn ast(source: &str) -> String {
+        match parse(source) {
+            Ok(statements) => format!("{:?}", statements),
+            Err(error) => panic!("{:?} should parse, got {:?}", source, error),
+        }
+    }
+
+    //解析失败时返回错误描述
+    fn error(source: &str) -> String {
+        match parse(source) {
+            Ok(_) => panic!("{:?} should not parse", source),
+            Err(AllError::ParseError(error)) => error.describe,
+            Err(AllError::ScanError(error)) => error.describe,
+            Err(AllError::NoContentError(error)) => error.describe,
+        }
+    }
+
+    #[test]
+    fn arrow_function_at_assignment_start() {
+        assert!(ast("f = (a, b) => a;").contains("ArrowFunctionExpression"));
+        assert!(ast("g(x => x, async (y) => y);").contains("is_async: true"));
+        assert!(ast("c ? x => 1 : y => 2;").contains("ConditionalExpression"));
+    }
+
+    #[test]
+    fn arrow_function_in_operand() {
+        error("a + x => x;");
+        error("a + (x) => x;");
+        error("-x => 1;");
+        error("x\n=> x;");
+        error("a = async (x)\n=> x;");
+    }
+
+    #[test]
+    fn shorthand_initializer_in_pattern() {
+        assert!(ast("({a = 1} = x);").contains("ObjectPattern"));
+        assert!(ast("[{a = 1}, ...{b = 2}] = x;").contains("ArrayPattern"));
+        assert!(ast("f = ({a = 1}) => a;").contains("ArrowFunctionExpression"));
+        assert!(ast("async ({a = 1}) => a;").contains("is_async: true"));
+        assert!(ast("for ({a = 1} of x) {}").contains("ForOfStatement"));
+    }
+
+    #[test]
+    fn shorthand_initializer_in_expression() {
+        let describe = "invalid shorthand property initializer";
+        assert_eq!(error("({a = 1});"), describe);
+        assert_eq!(error("x = {b: {a = 1}};"), describe);
+        assert_eq!(error("[{a = 1}];"), describe);
+        assert_eq!(error("f({a = 1});"), describe);
+        assert_eq!(error("async({a = 1});"), describe);
+        assert_eq!(error("[{a = 1}.b] = x;"), describe);
+    }
+
+    #[test]
+    fn array_pattern_rest_element() {
+        assert!(ast("[a, ...b] = c;").contains("ArrayPattern"));
+        assert!(ast("[a, ...b,];").contains("ArrayExpression"));
+        error("[...a,] = b;");
+        error("[...a, b] = c;");
+        error("([...a,]) => a;");
+    }
+
+    #[test]
+    fn class_constructor() {
+        assert!(ast("class A { constructor() {} }").contains("Constructor"));
+        assert!(ast("class A { 'constructor'() {} }").contains("Constructor"));
+        let computed = ast("class A { constructor() {} ['constructor']() {} }");
+        assert_eq!(computed.matches("Constructor").count(), 1);
+        let describe = "a class may only have one constructor";
+        assert_eq!(
+            error("class A { constructor() {} constructor() {} }"),
+            describe
+        );
+        assert_eq!(
+            error("class A { constructor() {} 'constructor'() {} }"),
+            describe
+        );
+        error("class A { get 'constructor'() {} }");
+        error("class A { 'constructor' = 1; }");
+    }
+
+    #[test]
+    fn labeled_continue() {
+        assert!(ast("l: do { continue l; } while (x);").contains("DoWhileStatement"));
+        assert!(ast("l: for (;;) { m: while (x) { continue l; } }").contains("LabeledStatement"));
+        error("l: { continue l; }");
+        error("l: if (x) { continue l; }");
+    }
+
+    #[test]
+    fn automatic_semicolon_insertion() {
+        assert!(ast("function f() { return\n1 }").contains("ReturnStatement { exp: None"));
+        assert!(ast("l: while (x) { break\nl }").contains("BreakStatement { label: None"));
+        assert!(ast("function* g() { yield\n1 }").contains("argument: None"));
+        let statements = parse("a\n++b").unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(format!("{:?}", statements[1]).contains("prefix: true"));
+        //( 之前的换行不会插入分号
+        let statements = parse("x\n(y)").unwrap();
+        assert_eq!(statements.len(), 1);
+        assert!(format!("{:?}", statements[0]).contains("CallExpression"));
+    }
+
+    #[test]
+    fn missing_semicolon() {
+        assert_eq!(error("a = 1 b = 2"), "\";\" is expected");
+        assert_eq!(error("a\n++\n;"), "unexpected token");
+    }
+
+    #[test]
+    fn break_and_continue_targets() {
+        assert!(ast("while (x) { break; }").contains("BreakStatement"));
+        assert!(ast("l: while (x) { break l; }").contains("label: Some"));
+        assert!(ast("l: { break l; }").contains("LabeledStatement"));
+    }
+
+    #[test]
+    fn illegal_break_and_continue() {
+        assert_eq!(error("break;"), "illegal break statement");
+        assert_eq!(error("continue;"), "illegal continue statement");
+        assert_eq!(
+            error("while (x) { (() => { break; }); }"),
+            "illegal break statement"
+        );
+        assert_eq!(error("l: while (x) { break m; }"), "undefined label \"m\"");
+    }
+
+    #[test]
+    fn declarations() {
+        let statement = ast("let a, b = 1, c;");
+        assert_eq!(statement.matches("VariableDeclarator").count(), 3);
+        assert!(ast("const a = 1, b = 2;").contains("kind: Const"));
+        assert!(ast("for (const a of b) {}").contains("ForOfStatement"));
+    }
+
+    #[test]
+    fn const_without_initializer() {
+        let describe = "missing initializer in const declaration";
+        assert_eq!(error("const a;"), describe);
+        assert_eq!(error("const a = 1, b;"), describe);
+        assert_eq!(error("for (const a;;) {}"), describe);
+    }
+
+    #[test]
+    fn switch_clauses() {
+        let statement = ast("switch (x) { case 1: case 2: break; default: y(); }");
+        assert_eq!(statement.matches("SwitchCase").count(), 3);
+        assert!(ast("switch (x) { default: case 1: }").contains("SwitchStatement"));
+    }
+
+    #[test]
+    fn duplicate_default_clause() {
+        let describe = "more than one default clause in switch statement";
+        assert_eq!(error("switch (x) { default: break; default: }"), describe);
+        assert_eq!(error("switch (x) { default: case 1: default: }"), describe);
+    }
+
+    #[test]
+    fn template_literals() {
+        let template = ast("`a${b}c${d}`;");
+        assert_eq!(template.matches("TemplateElement").count(), 3);
+        let tagged = ast("f`\\u{g}${a}\\xz`;");
+        assert!(tagged.contains("TaggedTemplateExpression"));
+        assert_eq!(tagged.matches("cooked: None").count(), 2);
+    }
+
+    #[test]
+    fn invalid_template_escape() {
+        let describe = "invalid escape sequence in template";
+        assert_eq!(error("`\\u{g}`;"), describe);
+        assert_eq!(error("`a${b}\\01`;"), describe);
+    }
+
+    #[test]
+    fn division_or_regex() {
+        let division = ast("a / b / c;");
+        assert_eq!(division.matches("BinaryExpression").count(), 2);
+        assert!(!division.contains("RegExpLiteral"));
+        assert!(ast("a /= 2;").contains("AssignmentExpression"));
+        assert!(ast("x = a\n/b/g;").contains("BinaryExpression"));
+        assert!(ast("if (x) /re/.test(y);").contains(r#"pattern: "re""#));
+        assert!(ast("x = /=/;").contains(r#"pattern: "=""#));
+        assert!(ast("x = /[//]/g;").contains(r#"pattern: "[//]""#));
+        assert_eq!(error("x = /a/é;"), "invalid regular expression flags");
+    }
+
+    #[test]
+    fn many_regex_literals() {
+        //每个正则只重新扫描自己，不会重新扫描后面的源码
+        let source = "x = /a/g;\n".repeat(8000);
+        assert_eq!(parse(&source).unwrap().len(), 8000);
+    }
+
+    #[test]
+    fn arrow_function_ends_assignment() {
+        error("x = () => {}(1);");
+        error("x = () => {}.a;");
+        error("x = () => {} + 1;");
+        error("() => {} ? 1 : 2;");
+        error("async () => {} * 2;");
+        assert_eq!(parse("f = () => {}\n(x)").unwrap().len(), 2);
+        assert!(ast("(() => {})(1);").contains("CallExpression"));
+        assert!(ast("x = (a)(b) + 1;").contains("BinaryExpression"));
+        assert!(ast("async(a) + 1;").contains("BinaryExpression"));
+    }
+
+    #[test]
+    fn identifiers_and_this() {
+        assert!(ast("foo;").contains(r#"exp: Identifier("foo")"#));
+        assert!(ast("async;").contains(r#"exp: Identifier("async")"#));
+        assert!(ast("this;").contains("exp: This"));
+        assert!(ast("x = this;").contains("AssignmentExpression"));
+    }
+
+    #[test]
+    fn keyword_is_not_an_identifier() {
+        assert_eq!(error("x = if;"), "unexpected token");
+        assert_eq!(error("x = );"), "unexpected token");
+    }
+
+    #[test]
+    fn call_expressions() {
+        let call = ast("f(1, a + b, g());");
+        assert!(
+            call.contains(r#"CallExpression { callee: PrimaryExpression { exp: Identifier("f")"#)
+        );
+        assert!(call.contains("BinaryExpression"));
+        assert_eq!(call.matches("CallExpression").count(), 2);
+        assert!(ast("f()();").contains("CallExpression { callee: CallExpression"));
+    }
+
+    #[test]
+    fn invalid_call_arguments() {
+        assert_eq!(error("f(1,,2);"), "unexpected token");
+        assert_eq!(error("f(1;"), "\")\" is expected");
+    }
+
+    #[test]
+    fn member_expressions() {
+        let chain = ast("a.b[c](d).e;");
+        assert!(chain.starts_with(
+            "[ExpressionStatement { exp: MemberExpression { object: CallExpression { \
+             callee: MemberExpression { object: MemberExpression { object: PrimaryExpression"
+        ));
+        assert_eq!(chain.matches("computed: true").count(), 1);
+        assert_eq!(chain.matches("computed: false").count(), 2);
+        //. 后面可以是关键字
+        assert!(ast("a.if;").contains(r#"Identifier("if")"#));
+    }
+
+    #[test]
+    fn invalid_member_expressions() {
+        assert_eq!(error("a.;"), "unexpected token");
+        assert_eq!(error("a[b;"), "\"]\" is expected");
+    }
+
+    #[test]
+    fn array_and_object_literals() {
+        let array = ast("x = [1, , 2,];");
+        assert!(array.contains("elements: [Some("));
+        assert_eq!(array.matches("Some(PrimaryExpression").count(), 2);
+        assert_eq!(array.matches(", None, ").count(), 1);
+        assert!(ast("x = [,];").contains("elements: [None]"));
+        let object = ast(r#"x = {a: 1, "b": 2, 3: c, [d]: e, f};"#);
+        assert_eq!(object.matches("Property(Property").count(), 5);
+        assert_eq!(object.matches("computed: true").count(), 1);
+        assert_eq!(object.matches("shorthand: true").count(), 1);
+    }
+
+    #[test]
+    fn invalid_array_and_object_literals() {
+        assert_eq!(error("[1 2];"), "\"]\" is expected");
+        assert_eq!(error("x = {a 1};"), "\":\" is expected");
+        assert_eq!(error("x = {a: 1,, b: 2};"), "unexpected token");
+    }
+
+    #[test]
+    fn compound_assignment() {
+        assert!(ast("a += 1;").contains("op: PlusAssign"));
+        assert!(ast("a.b -= 1;").contains("op: MinusAssign"));
+        //赋值是右结合的
+        let assignment = ast("a -= b *= 2;");
+        assert!(assignment
+            .contains("value: AssignmentExpression { op: AssignmentOperator { op: MultipAssign"));
+        assert!(ast("a[0] /= 2;").contains("AssignmentExpression"));
+    }
+
+    #[test]
+    fn invalid_assignment_target() {
+        let describe = "invalid assignment target";
+        assert_eq!(error("1 = a;"), describe);
+        assert_eq!(error("a + b = c;"), describe);
+        assert_eq!(error("f() += 1;"), describe);
+        assert_eq!(error("({a} += 1);"), describe);
+        assert_eq!(
+            error("++f();"),
+            "invalid left-hand side expression in update operation"
+        );
+    }
+
+    #[test]
+    fn logical_expressions() {
+        assert!(ast("a ?? b;").contains("op: Nullish"));
+        assert!(ast("(a || b) ?? c;").contains("op: Nullish"));
+        assert!(ast("a ?? (b && c);").contains("op: Nullish"));
+        //&& 的优先级比 || 高
+        let logical = ast("a || b && c;");
+        assert!(logical.contains("op: Or"));
+        assert!(logical.contains("right: LogicalExpression { op: LogicalOperator { op: And"));
+    }
+
+    #[test]
+    fn nullish_mixed_with_logical() {
+        let describe = "\"??\" cannot be mixed with \"&&\" or \"||\" without parentheses";
+        assert_eq!(error("a ?? b || c;"), describe);
+        assert_eq!(error("a && b ?? c;"), describe);
+        assert_eq!(error("a || b ?? c;"), describe);
+        assert_eq!(error("a ?? b && c;"), describe);
+    }
+
+    #[test]
+    fn conditional_expressions() {
+        //右结合：a ? b : c ? d : e
+        let conditional = ast("a ? b : c ? d : e;");
+        assert!(conditional.contains("alternate: ConditionalExpression"));
+        assert!(!conditional.contains("test: ConditionalExpression"));
+        let nested = ast("a ? b ? c : d : e;");
+        assert!(nested.contains("consequent: ConditionalExpression"));
+        assert!(ast("x = a ? b = 1 : c;").contains("consequent: AssignmentExpression"));
+    }
+
+    #[test]
+    fn invalid_conditional_expressions() {
+        assert_eq!(error("a ? b;"), "\":\" is expected");
+        assert_eq!(error("a ? : c;"), "unexpected token");
+    }
+
+    #[test]
+    fn loop_body_at_end_of_input() {
+        error("do");
+        error("while (x) do");
+        error("for (;;)");
+        error("for (a of b)");
+    }
+
+    #[test]
+    fn label_at_end_of_input() {
+        error("l:");
+        error("l: m:");
+    }
+
+    #[test]
+    fn function_parameters() {
+        let params = ast("function f(a, b = 1, [c], {d}, ...e) {}");
+        assert_eq!(params.matches("rest: false").count(), 4);
+        assert_eq!(params.matches("rest: true").count(), 1);
+        assert!(params.contains("ArrayPattern"));
+        assert!(params.contains("ObjectPattern"));
+        assert!(ast("function f(a = 1, {b: [c = 2]} = {}) {}").contains("ArrayPattern"));
+        assert!(ast("f = (a, b = 1, ...c) => a;").contains("rest: true"));
+    }
+
+    #[test]
+    fn invalid_function_parameters() {
+        error("function f(...a, b) {}");
+        error("function f(...a = 1) {}");
+        error("function f(a + 1) {}");
+        error("(a, ...b, c) => 1;");
+        error("(a.b) => 1;");
+    }
+
+    #[test]
+    fn try_statements() {
+        let optional = ast("try { a; } catch { b; }");
+        assert!(optional.contains("handler: Some(CatchClause { param: None"));
+        assert!(optional.contains("finalizer: None"));
+        let full = ast("try { a; } catch ({message}) { b; } finally { c; }");
+        assert!(full.contains("param: Some(ObjectPattern"));
+        assert!(full.contains("finalizer: Some(Block"));
+        assert!(ast("try {} finally {}").contains("handler: None"));
+        assert!(ast("throw new Error(x);").contains("ThrowStatement"));
+    }
+
+    #[test]
+    fn invalid_try_statements() {
+        assert_eq!(error("try {}"), "missing catch or finally after try");
+        assert_eq!(error("try {} catch () {}"), "unexpected token");
+        assert_eq!(error("throw\nx;"), "illegal newline after throw");
+    }
+
+    fn parse_module(source: &str) -> Result<Vec<Box<dyn StateMent>>, AllError> {
+        Parser::with_source_type(source, SourceType::Module).programing()
+    }
+
+    fn module(source: &str) -> String {
+        match parse_module(source) {
+            Ok(statements) => format!("{:?}", statements),
+            Err(error) => panic!("{:?} should parse, got {:?}", source, error),
+        }
+    }
+
+    fn module_error(source: &str) -> String {
+        match parse_module(source) {
+            Ok(_) => panic!("{:?} should not parse", source),
+            Err(AllError::ParseError(error)) => error.describe,
+            Err(AllError::ScanError(error)) => error.describe,
+            Err(AllError::NoContentError(error)) => error.describe,
+        }
+    }
+
+    #[test]
+    fn export_default_declaration() {
+        //函数和类的声明到 } 为止，后面是另一条语句
+        let statements = parse_module("export default function f() {} (1);").unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(format!("{:?}", statements[0]).contains("is_expression: false"));
+        assert_eq!(
+            parse_module("export default class {} [1];").unwrap().len(),
+            2
+        );
+        assert_eq!(
+            parse_module("export default async function () {}\n(1)")
+                .unwrap()
+                .len(),
+            2
+        );
+        let expression = module("export default (function () {})(1);");
+        assert!(expression.contains("CallExpression"));
+        assert!(expression.contains("is_expression: true"));
+        assert_eq!(module_error("export default 1 2;"), "\";\" is expected");
+    }
+
+    #[test]
+    fn import_reserved_binding() {
+        let describe = "unexpected reserved word \"await\"";
+        assert_eq!(module_error(r#"import await from "m";"#), describe);
+        assert_eq!(module_error(r#"import {a as await} from "m";"#), describe);
+        assert_eq!(module_error(r#"import {await} from "m";"#), describe);
+        assert_eq!(module_error(r#"import * as await from "m";"#), describe);
+        assert_eq!(module_error("let await = 1;"), describe);
+        assert!(module(r#"import {await as a} from "m";"#).contains("imported: \"await\""));
+    }
+
+    #[test]
+    fn import_declarations() {
+        let forms = [
+            r#"import "m";"#,
+            r#"import a from "m";"#,
+            r#"import * as ns from "m";"#,
+            r#"import {a, b as c, default as d} from "m";"#,
+            r#"import a, {b} from "m";"#,
+            r#"import a, * as ns from "m";"#,
+        ];
+        for source in forms {
+            assert!(module(source).contains("ImportDeclaration"));
+        }
+        assert!(module(r#"import a, * as ns from "m";"#).contains(r#"Namespace("ns")"#));
+        let named = module(r#"import {default as d} from "m";"#);
+        assert!(named.contains(r#"Named { imported: "default", local: "d" }"#));
+    }
+
+    #[test]
+    fn export_declarations() {
+        let forms = [
+            r#"export * from "m";"#,
+            r#"export * as ns from "m";"#,
+            "export {a, b as c};",
+            r#"export {default} from "m";"#,
+            "export {a as default};",
+            "export const a = 1, b = 2;",
+            "export function f() {}",
+            "export async function f() {}",
+            "export class A {}",
+            "export default 1;",
+        ];
+        for source in forms {
+            assert!(module(source).contains("ExportDeclaration"));
+        }
+    }
+
+    #[test]
+    fn invalid_module_items() {
+        assert_eq!(
+            module_error(r#"import {default} from "m";"#),
+            "unexpected token"
+        );
+        assert_eq!(
+            module_error("import a from b;"),
+            "module specifier is expected"
+        );
+        assert_eq!(module_error(r#"import * from "m";"#), "\"as\" is expected");
+        assert_eq!(module_error("export {if};"), "unexpected token");
+        assert_eq!(
+            module_error("export {a} from;"),
+            "module specifier is expected"
+        );
+        assert_eq!(
+            module_error(r#"if (x) { import a from "m"; }"#),
+            "import may only appear at the top level of a module"
+        );
+        assert_eq!(
+            module_error("function f() { export {a}; }"),
+            "export may only appear at the top level of a module"
+        );
+    }
+
+    #[test]
+    fn module_items_in_script() {
+        assert_eq!(
+            error(r#"import a from "m";"#),
+            "cannot use import outside a module"
+        );
+        assert_eq!(
+            error("export const a = 1;"),
+            "cannot use export outside a module"
+        );
+        //await 只在模块中是关键字
+        assert!(ast("await;").contains(r#"Identifier("await")"#));
+        assert!(module("await x;").contains("AwaitExpression"));
+    }
+
+    #[test]
+    fn object_methods() {
+        assert!(ast("o = { m() {} };").contains("MethodDefinition"));
+        assert!(ast("o = { get a() { return 1 }, set a(v) {} };").contains("kind: Set"));
+        assert!(ast("o = { async m() { await 1 } };").contains("is_async: true"));
+        assert!(ast("o = { *g() { yield 1 } };").contains("is_generator: true"));
+        assert!(ast("o = { [k]() { super.x; } };").contains("computed: true"));
+        //get、set、async 后面紧跟 , : ( } 时是属性名
+        let properties = ast("o = { get: 1, set, async() {} };");
+        assert_eq!(properties.matches("MethodDefinition").count(), 1);
+        assert_eq!(
+            error("o = { get a(x) {} };"),
+            "getter must not have parameters"
+        );
+        error("o = { m() { super(); } };");
+        error("o = { async\nm() {} };");
+        error("({ m() {} } = x);");
+    }
+
+    #[test]
+    fn await_and_yield_in_arrow_parameters() {
+        let describe = "arrow function parameters must not contain await or yield";
+        assert_eq!(
+            error("async function f() { (a = await 1) => a; }"),
+            describe
+        );
+        assert_eq!(
+            error("async function f() { async (a = await 1) => a; }"),
+            describe
+        );
+        assert_eq!(error("function* g() { (a = yield) => a; }"), describe);
+        assert_eq!(error("function* g() { ([a = yield]) => a; }"), describe);
+        //不是箭头函数的参数，或者在内层函数中，都是允许的
+        assert!(ast("async function f() { (a = await 1); async(await 1); }")
+            .contains("AwaitExpression"));
+        assert!(
+            ast("function* g() { (a = function* () { yield 1 }) => a; }")
+                .contains("YieldExpression")
+        );
+    }
+
+    #[test]
+    fn crlf_line_terminators() {
+        assert_eq!(parse("a = 1\r\nb = 2").unwrap().len(), 2);
+        assert_eq!(parse("a = 1\rb = 2\u{2028}c = 3").unwrap().len(), 3);
+        assert!(ast("function f() { return\r\n1 }").contains("ReturnStatement { exp: None"));
+        assert_eq!(error("a = 1;\r\nb = 2;\r\nc d"), "\";\" is expected");
+        assert_eq!(error("x = 1 # 2;"), "unexpected char");
+    }
+}
@@ -1,8 +1,11 @@
 use self::error::ScanError;
 
-use super::utils::{is_alpha, is_alphadigital, is_digital, is_space};
+use super::utils::{
+    is_alpha, is_alphadigital, is_digital, is_hex_digital, is_identifier_part, is_line_terminator,
+    is_space,
+};
 use lazy_static::lazy_static;
-use std::{collections::HashMap, error::Error, iter::Peekable, ops::Deref, str::Chars};
+use std::{collections::HashMap, iter::Peekable, ops::Deref, str::Chars};
 
 pub mod error {
     use super::Position;
@@ -28,32 +31,72 @@ pub mod error {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenRow {
     Dot,
+    Ellipsis,
     Comma,
     Semicolon, //分号
+    Colon,
     Plus,
     Minus,
     Start,
     Div,
+    PlusEq,
+    MinusEq,
+    Increment,
+    Decrement,
+    StartEq,
+    DivEq,
     Eq,
     DoubleEq,
+    Arrow,
     Exclamation,
     NotEq,
     Greater,
     Less,
     GreaterEq,
     LessEq,
+    DoubleAnd,
+    DoubleOr,
+    DoubleQuestion,
+    Question,
     LeftParent,
     RightParent,
     LeftBrace,
     RightBrace,
-    Digital(f64),
-    String(String),
+    LeftBracket,
+    RightBracket,
+    Digital {
+        value: f64,
+        raw: String, //源码中的原文
+    },
+    BigInt(String), //源码中的原文，包括结尾的 n
+    //模板字符串按 ${ } 切成几段：`a`、`a${、}a${、}a`
+    TemplateString(TemplatePart),
+    TemplateHead(TemplatePart),
+    TemplateMiddle(TemplatePart),
+    TemplateTail(TemplatePart),
+    //正则表达式 /pattern/flags
+    RegExp {
+        pattern: String,
+        flags: String,
+    },
+    String {
+        value: String, //转义之后的值
+        raw: String,   //源码中的原文，包括引号
+    },
     Space(String),
+    //注释，text 不包括 // 和 /* */，end 是注释最后一个字符的位置
+    Comment {
+        text: String,
+        block: bool,
+        end: Position,
+    },
 
     Identifier(String),
 
     // 关键字：
     Let,
+    Const,
+    Var,
     Function,
     Return,
     If,
@@ -66,35 +109,81 @@ pub enum TokenRow {
     False,
     Null,
     This,
+    Class,
+    Extends,
+    Super,
+    Static,
+    New,
+    Try,
+    Catch,
+    Finally,
+    Throw,
+    Switch,
+    Case,
+    Default,
+    Do,
+    In,
+    Import,
+    Export,
 }
 
 impl TokenRow {
     pub fn to_string(&self) -> String {
         match self {
             TokenRow::Dot => ".".to_string(),
+            TokenRow::Ellipsis => "...".to_string(),
             TokenRow::Comma => ",".to_string(),
             TokenRow::Semicolon => ";".to_string(),
+            TokenRow::Colon => ":".to_string(),
             TokenRow::Plus => "+".to_string(),
             TokenRow::Minus => "-".to_string(),
             TokenRow::Start => "*".to_string(),
             TokenRow::Div => "/".to_string(),
+            TokenRow::PlusEq => "+=".to_string(),
+            TokenRow::MinusEq => "-=".to_string(),
+            TokenRow::Increment => "++".to_string(),
+            TokenRow::Decrement => "--".to_string(),
+            TokenRow::StartEq => "*=".to_string(),
+            TokenRow::DivEq => "/=".to_string(),
             TokenRow::Eq => "=".to_string(),
             TokenRow::DoubleEq => "==".to_string(),
+            TokenRow::Arrow => "=>".to_string(),
             TokenRow::Exclamation => "!".to_string(),
             TokenRow::NotEq => "!=".to_string(),
             TokenRow::Greater => ">".to_string(),
             TokenRow::Less => "<".to_string(),
             TokenRow::GreaterEq => ">=".to_string(),
             TokenRow::LessEq => "<=".to_string(),
+            TokenRow::DoubleAnd => "&&".to_string(),
+            TokenRow::DoubleOr => "||".to_string(),
+            TokenRow::DoubleQuestion => "??".to_string(),
+            TokenRow::Question => "?".to_string(),
             TokenRow::LeftParent => "(".to_string(),
             TokenRow::RightParent => ")".to_string(),
             TokenRow::LeftBrace => "{".to_string(),
             TokenRow::RightBrace => "}".to_string(),
-            TokenRow::Digital(digital) => digital.to_string(),
-            TokenRow::String(string) => string.to_string(),
+            TokenRow::LeftBracket => "[".to_string(),
+            TokenRow::RightBracket => "]".to_string(),
+            TokenRow::Digital { raw, .. } => raw.to_string(),
+            TokenRow::BigInt(raw) => raw.to_string(),
+            TokenRow::String { raw, .. } => raw.to_string(),
+            TokenRow::TemplateString(part) => format!("`{}`", part.raw),
+            TokenRow::TemplateHead(part) => format!("`{}${{", part.raw),
+            TokenRow::TemplateMiddle(part) => format!("}}{}${{", part.raw),
+            TokenRow::TemplateTail(part) => format!("}}{}`", part.raw),
+            TokenRow::RegExp { pattern, flags } => format!("/{}/{}", pattern, flags),
             TokenRow::Space(space) => space.to_string(),
+            TokenRow::Comment { text, block, .. } => {
+                if *block {
+                    format!("/*{}*/", text)
+                } else {
+                    format!("//{}", text)
+                }
+            }
             TokenRow::Identifier(s) => s.to_string(),
             TokenRow::Let => "let".to_string(),
+            TokenRow::Const => "const".to_string(),
+            TokenRow::Var => "var".to_string(),
             TokenRow::Function => "function".to_string(),
             TokenRow::Return => "return".to_string(),
             TokenRow::If => "if".to_string(),
@@ -107,6 +196,40 @@ impl TokenRow {
             TokenRow::False => "false".to_string(),
             TokenRow::Null => "null".to_string(),
             TokenRow::This => "this".to_string(),
+            TokenRow::Class => "class".to_string(),
+            TokenRow::Extends => "extends".to_string(),
+            TokenRow::Super => "super".to_string(),
+            TokenRow::Static => "static".to_string(),
+            TokenRow::New => "new".to_string(),
+            TokenRow::Try => "try".to_string(),
+            TokenRow::Catch => "catch".to_string(),
+            TokenRow::Finally => "finally".to_string(),
+            TokenRow::Throw => "throw".to_string(),
+            TokenRow::Switch => "switch".to_string(),
+            TokenRow::Case => "case".to_string(),
+            TokenRow::Default => "default".to_string(),
+            TokenRow::Do => "do".to_string(),
+            TokenRow::In => "in".to_string(),
+            TokenRow::Import => "import".to_string(),
+            TokenRow::Export => "export".to_string(),
+        }
+    }
+
+    pub fn is_identifier_name(&self) -> bool {
+        //标识符或者关键字，可以作为属性名
+        match self {
+            TokenRow::Identifier(_) => true,
+            TokenRow::Digital { .. }
+            | TokenRow::BigInt(_)
+            | TokenRow::String { .. }
+            | TokenRow::TemplateString(_)
+            | TokenRow::TemplateHead(_)
+            | TokenRow::TemplateMiddle(_)
+            | TokenRow::TemplateTail(_)
+            | TokenRow::RegExp { .. }
+            | TokenRow::Space(_)
+            | TokenRow::Comment { .. } => false,
+            t => IDENTIFER_MAP.contains_key(&*t.to_string()),
         }
     }
 }
@@ -123,6 +246,8 @@ lazy_static! {
     static ref IDENTIFER_MAP: HashMap<&'static str, TokenRow> = {
         let mut map = HashMap::new();
         map.insert("let", TokenRow::Let);
+        map.insert("const", TokenRow::Const);
+        map.insert("var", TokenRow::Var);
         map.insert("function", TokenRow::Function);
         map.insert("return", TokenRow::Return);
         map.insert("if", TokenRow::If);
@@ -135,10 +260,42 @@ lazy_static! {
         map.insert("false", TokenRow::False);
         map.insert("null", TokenRow::Null);
         map.insert("this", TokenRow::This);
+        map.insert("class", TokenRow::Class);
+        map.insert("extends", TokenRow::Extends);
+        map.insert("super", TokenRow::Super);
+        map.insert("static", TokenRow::Static);
+        map.insert("new", TokenRow::New);
+        map.insert("try", TokenRow::Try);
+        map.insert("catch", TokenRow::Catch);
+        map.insert("finally", TokenRow::Finally);
+        map.insert("throw", TokenRow::Throw);
+        map.insert("switch", TokenRow::Switch);
+        map.insert("case", TokenRow::Case);
+        map.insert("default", TokenRow::Default);
+        map.insert("do", TokenRow::Do);
+        map.insert("in", TokenRow::In);
+        map.insert("import", TokenRow::Import);
+        map.insert("export", TokenRow::Export);
         map
     };
 }
 
+//模板字符串的一段，raw 不包括 ` ${ } 这些分隔符
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplatePart {
+    pub cooked: Option<String>, //含有非法转义时为 None，只有带标签的模板允许
+    pub raw: String,
+    pub end: Position,
+}
+
+//词法目标，RegExp 时 / 开头的 token 按正则扫描，否则是除号，由解析器根据上下文设置
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Goal {
+    #[default]
+    Div,
+    RegExp,
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Position {
     pub row: usize,
@@ -155,18 +312,28 @@ impl Position {
 pub struct Token {
     pub token: TokenRow,
     pub position: Position,
+    pub line_break: bool, //和前一个 token 之间是否有换行，用于自动插入分号
 }
 impl Token {
     pub fn new(token: TokenRow, position: Position) -> Self {
-        Self { token, position }
+        Self {
+            token,
+            position,
+            line_break: false,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Scanner<'a> {
     source: Peekable<Chars<'a>>,
     current_string: String,
     position: Position,
+    line_break: bool,
+    goal: Goal,
+    brace_depth: usize,
+    //每个未结束的 ${ 开始时的 brace_depth，遇到同样深度的 } 时回到模板中
+    template_depth: Vec<usize>,
 }
 
 impl<'a> Scanner<'a> {
@@ -175,6 +342,10 @@ impl<'a> Scanner<'a> {
             source: source.chars().peekable(),
             current_string: String::new(),
             position: Position::default(),
+            line_break: false,
+            goal: Goal::Div,
+            brace_depth: 0,
+            template_depth: vec![],
         }
     }
 
@@ -184,6 +355,11 @@ impl<'a> Scanner<'a> {
         self.position.clone()
     }
 
+    pub fn set_goal(&mut self, goal: Goal) {
+        //只对下一个 token 有效
+        self.goal = goal;
+    }
+
     pub fn clear(&mut self) {
         self.current_string.clear();
     }
@@ -193,7 +369,8 @@ impl<'a> Scanner<'a> {
         match ch {
             Some(c) => {
                 self.current_string.push(c);
-                if c == '\n' {
+                //\r\n 只算一次换行
+                if is_line_terminator(c) && !(c == '\r' && self.next_is_expected('\n')) {
                     self.position.col = 0;
                     self.position.row += 1;
                 } else {
@@ -223,30 +400,366 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    pub fn advance_until(&mut self, expected: char) {
-        //一直推进 直到遇见expected（expected不会被推进）
-        while !self.next_is_expected(expected) {
+    pub fn advance_until_by(&mut self, func: &dyn Fn(char) -> bool) {
+        while self.next_is_expected_by(func) {
             self.advance();
         }
     }
 
-    pub fn advance_until_by(&mut self, func: &dyn Fn(char) -> bool) {
-        while self.next_is_expected_by(func) {
+    pub fn get_number(&mut self, first: char, start: Position) -> Result<TokenRow, ScanError> {
+        //first 是已经读过的第一个字符（数字或者 .）
+        let invalid = |describe: &str| ScanError {
+            code: 300,
+            describe: describe.to_string(),
+            position: start.clone(),
+        };
+        let radix = match (first, self.get_next()) {
+            ('0', Some('x' | 'X')) => 16,
+            ('0', Some('o' | 'O')) => 8,
+            ('0', Some('b' | 'B')) => 2,
+            _ => 10,
+        };
+        let token_row = if radix != 10 {
             self.advance();
+            let digits = self
+                .get_digits(radix, false)
+                .ok_or_else(|| invalid("invalid numeric separator"))?;
+            if digits.is_empty() {
+                return Err(invalid("invalid numeric literal"));
+            }
+            if self.next_is_expected('n') {
+                self.advance();
+                TokenRow::BigInt(self.current_string.clone())
+            } else {
+                TokenRow::Digital {
+                    value: digits_value(&digits, radix),
+                    raw: self.current_string.clone(),
+                }
+            }
+        } else if first == '0' && self.next_is_expected_by(&is_digital) {
+            //0 开头的旧式八进制（017），含有 8、9 时按十进制处理（019）
+            self.advance_until_by(&is_digital);
+            let digits = self.current_string.clone();
+            let radix = if digits.chars().all(|ch| ch < '8') {
+                8
+            } else {
+                10
+            };
+            TokenRow::Digital {
+                value: digits_value(&digits, radix),
+                raw: digits,
+            }
+        } else {
+            //十进制：整数部分、小数部分、指数部分，去掉分隔符之后交给 f64 解析
+            let mut text = first.to_string();
+            let mut is_integer = first != '.';
+            let separator = || invalid("invalid numeric separator");
+            if first == '.' {
+                text += &self.get_digits(10, false).ok_or_else(separator)?;
+            } else {
+                if first != '0' {
+                    text += &self.get_digits(10, true).ok_or_else(separator)?;
+                }
+                if self.next_is_expected('.') {
+                    self.advance();
+                    is_integer = false;
+                    text.push('.');
+                    text += &self.get_digits(10, false).ok_or_else(separator)?;
+                }
+            }
+            if self.next_is_expected_by(&|ch| ch == 'e' || ch == 'E') {
+                self.advance();
+                is_integer = false;
+                text.push('e');
+                if let Some(&sign @ ('+' | '-')) = self.get_next() {
+                    self.advance();
+                    text.push(sign);
+                }
+                let exponent = self.get_digits(10, false).ok_or_else(separator)?;
+                if exponent.is_empty() {
+                    return Err(invalid("invalid numeric literal"));
+                }
+                text += &exponent;
+            }
+            if is_integer && self.next_is_expected('n') {
+                self.advance();
+                TokenRow::BigInt(self.current_string.clone())
+            } else {
+                TokenRow::Digital {
+                    value: text
+                        .parse()
+                        .map_err(|_| invalid("invalid numeric literal"))?,
+                    raw: self.current_string.clone(),
+                }
+            }
+        };
+        //数字后面不能紧跟标识符或者数字，例如 3in、0b12
+        if self.next_is_expected_by(&is_alphadigital) {
+            return Err(invalid(
+                "identifier starts immediately after numeric literal",
+            ));
         }
+        Ok(token_row)
     }
 
-    pub fn get_number(&mut self) -> Result<f64, impl Error> {
-        self.advance_until_by(&is_digital);
-        let digital = self.current_string.clone();
-        digital.parse::<f64>()
+    fn get_digits(&mut self, radix: u32, after_digit: bool) -> Option<String> {
+        //读取 radix 进制的数字，_ 只能出现在两个数字之间，返回去掉 _ 的数字
+        let mut digits = String::new();
+        let mut after_digit = after_digit;
+        loop {
+            match self.get_next() {
+                Some('_') => {
+                    if !after_digit {
+                        return None;
+                    }
+                    self.advance();
+                    if !self.next_is_expected_by(&|ch| ch.is_digit(radix)) {
+                        return None;
+                    }
+                    after_digit = false;
+                }
+                Some(ch) if ch.is_digit(radix) => {
+                    digits.push(*ch);
+                    self.advance();
+                    after_digit = true;
+                }
+                _ => return Some(digits),
+            }
+        }
     }
 
-    pub fn get_string(&mut self) -> String {
-        self.advance_until('"');
-        let s = self.current_string.clone();
-        self.advance();
-        s
+    pub fn get_string(&mut self, quote: char, start: Position) -> Result<String, ScanError> {
+        //开头的引号已经读过，返回转义之后的值，原文留在 current_string 中
+        let mut value = String::new();
+        let mut high_surrogate = None;
+        loop {
+            let position = self.get_position();
+            let ch = match self.advance() {
+                None | Some('\n') | Some('\r') => {
+                    return Err(ScanError {
+                        code: 300,
+                        describe: "unterminated string literal".to_string(),
+                        position: start,
+                    })
+                }
+                Some(ch) if ch == quote => {
+                    push_code_point(&mut value, &mut high_surrogate, None);
+                    return Ok(value);
+                }
+                Some(ch) => ch,
+            };
+            let code = if ch == '\\' {
+                match self.get_escape(position)? {
+                    Some(code) => code,
+                    None => continue,
+                }
+            } else {
+                ch as u32
+            };
+            push_code_point(&mut value, &mut high_surrogate, Some(code));
+        }
+    }
+
+    pub fn get_template(&mut self, start: Position) -> Result<(TemplatePart, bool), ScanError> {
+        //开头的 ` 或者 } 已经读过，读到 ` 或者 ${ 为止，返回的 bool 表示是否以 ${ 结束
+        let mut value = String::new();
+        let mut high_surrogate = None;
+        let mut valid = true;
+        let substitution = loop {
+            let position = self.get_position();
+            let ch = match self.advance() {
+                None => {
+                    return Err(ScanError {
+                        code: 300,
+                        describe: "unterminated template literal".to_string(),
+                        position: start,
+                    })
+                }
+                Some('`') => break false,
+                Some('$') if self.next_is_expected('{') => {
+                    self.advance();
+                    break true;
+                }
+                Some(ch) => ch,
+            };
+            let code = match ch {
+                //模板中不允许八进制转义，非法的转义不是错误，只是没有 cooked 值
+                '\\' if self.next_is_expected_by(&is_digital) => match self.advance() {
+                    Some('0') if !self.next_is_expected_by(&is_digital) => 0,
+                    _ => {
+                        valid = false;
+                        continue;
+                    }
+                },
+                '\\' => match self.get_escape(position) {
+                    Ok(Some(code)) => code,
+                    Ok(None) => continue,
+                    Err(_) => {
+                        valid = false;
+                        continue;
+                    }
+                },
+                //\r\n 和 \r 都当作 \n
+                '\r' => {
+                    if self.next_is_expected('\n') {
+                        self.advance();
+                    }
+                    '\n' as u32
+                }
+                ch => ch as u32,
+            };
+            push_code_point(&mut value, &mut high_surrogate, Some(code));
+        };
+        push_code_point(&mut value, &mut high_surrogate, None);
+        let len = self.current_string.len() - if substitution { 2 } else { 1 };
+        let raw = self.current_string[1..len]
+            .replace("\r\n", "\n")
+            .replace('\r', "\n");
+        let part = TemplatePart {
+            cooked: if valid { Some(value) } else { None },
+            raw,
+            end: Position::new(self.position.row, self.position.col - 1),
+        };
+        Ok((part, substitution))
+    }
+
+    fn get_escape(&mut self, position: Position) -> Result<Option<u32>, ScanError> {
+        //\ 之后的转义序列，返回字符的码点，续行（\ 加换行）返回 None
+        let ch = match self.advance() {
+            Some(ch) => ch,
+            None => return Ok(None),
+        };
+        let escaped = match ch {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            //旧式的八进制转义 \1、\01、\377，0-3 开头时最多三位，否则最多两位；\8、\9 就是字符本身
+            '0'..='7' => {
+                let mut code = ch.to_digit(8).unwrap();
+                let count = if ch <= '3' { 3 } else { 2 };
+                for _ in 1..count {
+                    if !self.next_is_expected_by(&|ch| ch.is_digit(8)) {
+                        break;
+                    }
+                    code = code * 8 + self.advance().unwrap().to_digit(8).unwrap();
+                }
+                return Ok(Some(code));
+            }
+            '\n' => return Ok(None),
+            '\r' => {
+                if self.next_is_expected('\n') {
+                    self.advance();
+                }
+                return Ok(None);
+            }
+            'x' => return self.get_hex_escape(2, position).map(Some),
+            'u' if self.next_is_expected('{') => {
+                self.advance();
+                let mut code: u32 = 0;
+                let mut count = 0;
+                while self.next_is_expected_by(&is_hex_digital) {
+                    let digit = self.advance().unwrap().to_digit(16).unwrap();
+                    code = code.saturating_mul(16).saturating_add(digit);
+                    count += 1;
+                }
+                if count == 0 || code > 0x10FFFF || !self.next_is_expected('}') {
+                    return Err(ScanError {
+                        code: 300,
+                        describe: "invalid unicode escape sequence".to_string(),
+                        position,
+                    });
+                }
+                self.advance();
+                return Ok(Some(code));
+            }
+            'u' => return self.get_hex_escape(4, position).map(Some),
+            ch => ch,
+        };
+        Ok(Some(escaped as u32))
+    }
+
+    fn get_hex_escape(&mut self, count: usize, position: Position) -> Result<u32, ScanError> {
+        let mut code = 0;
+        for _ in 0..count {
+            if !self.next_is_expected_by(&is_hex_digital) {
+                let describe = if count == 2 {
+                    "invalid hexadecimal escape sequence"
+                } else {
+                    "invalid unicode escape sequence"
+                };
+                return Err(ScanError {
+                    code: 300,
+                    describe: describe.to_string(),
+                    position,
+                });
+            }
+            code = code * 16 + self.advance().unwrap().to_digit(16).unwrap();
+        }
+        Ok(code)
+    }
+
+    pub fn get_regex(&mut self, start: Position) -> Result<TokenRow, ScanError> {
+        //开头的 / 已经读过，[] 中的 / 不会结束正则
+        let unterminated = || ScanError {
+            code: 300,
+            describe: "unterminated regular expression literal".to_string(),
+            position: start.clone(),
+        };
+        let mut in_class = false;
+        loop {
+            match self.advance() {
+                None => return Err(unterminated()),
+                Some(ch) if is_line_terminator(ch) => return Err(unterminated()),
+                Some('\\') => {
+                    if !self.next_is_expected_by(&|ch| !is_line_terminator(ch)) {
+                        return Err(unterminated());
+                    }
+                    self.advance();
+                }
+                Some('[') => in_class = true,
+                Some(']') => in_class = false,
+                Some('/') if !in_class => break,
+                _ => {}
+            }
+        }
+        let pattern = self.current_string[1..self.current_string.len() - 1].to_string();
+        let mut flags = String::new();
+        //flag 后面紧跟的标识符字符（如 /a/é）也会被读进来，然后报错
+        while self.next_is_expected_by(&is_identifier_part) {
+            let flag = self.advance().unwrap();
+            //每个 flag 只能出现一次，u 和 v 不能同时出现
+            let conflict = match flag {
+                'u' => flags.contains('v'),
+                'v' => flags.contains('u'),
+                _ => false,
+            };
+            if !"dgimsuyv".contains(flag) || flags.contains(flag) || conflict {
+                return Err(ScanError {
+                    code: 300,
+                    describe: "invalid regular expression flags".to_string(),
+                    position: start,
+                });
+            }
+            flags.push(flag);
+        }
+        Ok(TokenRow::RegExp { pattern, flags })
+    }
+
+    pub fn get_block_comment(&mut self) -> Option<String> {
+        //已经读过 /*，读到 */ 为止，没有 */ 时返回 None
+        loop {
+            match self.advance()? {
+                '*' if self.next_is_expected('/') => {
+                    self.advance();
+                    let len = self.current_string.len();
+                    return Some(self.current_string[2..len - 2].to_string());
+                }
+                _ => continue,
+            }
+        }
     }
 
     pub fn get_identifier(&mut self) -> String {
@@ -265,26 +778,156 @@ impl<'a> Scanner<'a> {
         let position = self.get_position();
         let ch = self.advance();
         let token_row = match ch {
-            Some('.') => TokenRow::Dot,
+            Some('.') if self.next_is_expected_by(&is_digital) => {
+                match self.get_number('.', position.clone()) {
+                    Ok(token_row) => token_row,
+                    Err(err) => {
+                        self.clear();
+                        return Some(Err(err));
+                    }
+                }
+            }
+            Some('.') => {
+                if let Some(&'.') = self.get_next() {
+                    self.advance();
+                    if let Some(&'.') = self.get_next() {
+                        self.advance();
+                        TokenRow::Ellipsis
+                    } else {
+                        self.clear();
+                        return Some(Err(position.into()));
+                    }
+                } else {
+                    TokenRow::Dot
+                }
+            }
             Some(',') => TokenRow::Comma,
             Some(';') => TokenRow::Semicolon,
-            Some('+') => TokenRow::Plus,
-            Some('-') => TokenRow::Minus,
-            Some('*') => TokenRow::Start,
-            Some('/') => TokenRow::Div,
-            Some('(') => TokenRow::LeftParent,
-            Some(')') => TokenRow::RightParent,
-            Some('{') => TokenRow::LeftBrace,
-            Some('}') => TokenRow::RightBrace,
-            Some(' ') | Some('\n') | Some('\t') => TokenRow::Space(self.get_space()),
-            Some('=') => {
+            Some(':') => TokenRow::Colon,
+            Some('+') => match self.get_next() {
+                Some(&'=') => {
+                    self.advance();
+                    TokenRow::PlusEq
+                }
+                Some(&'+') => {
+                    self.advance();
+                    TokenRow::Increment
+                }
+                _ => TokenRow::Plus,
+            },
+            Some('-') => match self.get_next() {
+                Some(&'=') => {
+                    self.advance();
+                    TokenRow::MinusEq
+                }
+                Some(&'-') => {
+                    self.advance();
+                    TokenRow::Decrement
+                }
+                _ => TokenRow::Minus,
+            },
+            Some('*') => {
                 if let Some(&'=') = self.get_next() {
                     self.advance();
-                    TokenRow::DoubleEq
+                    TokenRow::StartEq
                 } else {
-                    TokenRow::Eq
+                    TokenRow::Start
+                }
+            }
+            Some('/')
+                if self.goal == Goal::RegExp && !matches!(self.get_next(), Some('/' | '*')) =>
+            {
+                match self.get_regex(position.clone()) {
+                    Ok(token_row) => token_row,
+                    Err(err) => {
+                        self.clear();
+                        return Some(Err(err));
+                    }
+                }
+            }
+            Some('/') => match self.get_next() {
+                Some(&'=') => {
+                    self.advance();
+                    TokenRow::DivEq
+                }
+                Some(&'/') => {
+                    self.advance_until_by(&|ch| !is_line_terminator(ch));
+                    let text = self.current_string[2..].to_string();
+                    TokenRow::Comment {
+                        text,
+                        block: false,
+                        end: Position::new(self.position.row, self.position.col - 1),
+                    }
+                }
+                Some(&'*') => {
+                    self.advance();
+                    match self.get_block_comment() {
+                        Some(text) => TokenRow::Comment {
+                            text,
+                            block: true,
+                            end: Position::new(self.position.row, self.position.col - 1),
+                        },
+                        None => {
+                            self.clear();
+                            return Some(Err(ScanError {
+                                code: 300,
+                                describe: "unterminated comment".to_string(),
+                                position,
+                            }));
+                        }
+                    }
+                }
+                _ => TokenRow::Div,
+            },
+            Some('(') => TokenRow::LeftParent,
+            Some(')') => TokenRow::RightParent,
+            Some('{') => {
+                self.brace_depth += 1;
+                TokenRow::LeftBrace
+            }
+            //${ } 中的 } 回到模板中继续读
+            Some('}') if self.template_depth.last() == Some(&self.brace_depth) => {
+                match self.get_template(position.clone()) {
+                    Ok((part, true)) => TokenRow::TemplateMiddle(part),
+                    Ok((part, false)) => {
+                        self.template_depth.pop();
+                        TokenRow::TemplateTail(part)
+                    }
+                    Err(err) => {
+                        self.clear();
+                        return Some(Err(err));
+                    }
                 }
             }
+            Some('}') => {
+                self.brace_depth = self.brace_depth.saturating_sub(1);
+                TokenRow::RightBrace
+            }
+            Some('`') => match self.get_template(position.clone()) {
+                Ok((part, true)) => {
+                    self.template_depth.push(self.brace_depth);
+                    TokenRow::TemplateHead(part)
+                }
+                Ok((part, false)) => TokenRow::TemplateString(part),
+                Err(err) => {
+                    self.clear();
+                    return Some(Err(err));
+                }
+            },
+            Some('[') => TokenRow::LeftBracket,
+            Some(']') => TokenRow::RightBracket,
+            Some(c) if is_space(c) => TokenRow::Space(self.get_space()),
+            Some('=') => match self.get_next() {
+                Some(&'=') => {
+                    self.advance();
+                    TokenRow::DoubleEq
+                }
+                Some(&'>') => {
+                    self.advance();
+                    TokenRow::Arrow
+                }
+                _ => TokenRow::Eq,
+            },
             Some('!') => {
                 if let Some(&'=') = self.get_next() {
                     self.advance();
@@ -309,14 +952,49 @@ impl<'a> Scanner<'a> {
                     TokenRow::Less
                 }
             }
-            Some('"') => {
-                let s = self.get_string();
-                TokenRow::String(s)
+            Some('&') => {
+                if let Some(&'&') = self.get_next() {
+                    self.advance();
+                    TokenRow::DoubleAnd
+                } else {
+                    self.clear();
+                    return Some(Err(position.into()));
+                }
             }
-            Some(c) if is_digital(c) => {
-                let dig = self.get_number().unwrap();
-                TokenRow::Digital(dig)
+            Some('|') => {
+                if let Some(&'|') = self.get_next() {
+                    self.advance();
+                    TokenRow::DoubleOr
+                } else {
+                    self.clear();
+                    return Some(Err(position.into()));
+                }
             }
+            Some('?') => {
+                if let Some(&'?') = self.get_next() {
+                    self.advance();
+                    TokenRow::DoubleQuestion
+                } else {
+                    TokenRow::Question
+                }
+            }
+            Some(quote @ ('"' | '\'')) => match self.get_string(quote, position.clone()) {
+                Ok(value) => TokenRow::String {
+                    value,
+                    raw: self.current_string.clone(),
+                },
+                Err(err) => {
+                    self.clear();
+                    return Some(Err(err));
+                }
+            },
+            Some(c) if is_digital(c) => match self.get_number(c, position.clone()) {
+                Ok(token_row) => token_row,
+                Err(err) => {
+                    self.clear();
+                    return Some(Err(err));
+                }
+            },
 
             Some(c) if is_alpha(c) => {
                 let identifer = self.get_identifier();
@@ -327,13 +1005,32 @@ impl<'a> Scanner<'a> {
                 }
             }
 
-            _ => {
+            Some(_) => {
+                self.clear();
+                return Some(Err(position.into()));
+            }
+            None => {
                 self.clear();
                 return None;
             }
         };
         self.clear();
-        Some(Ok(Token::new(token_row, position)))
+        //空白和注释会被解析器过滤掉，把其中的换行记在下一个 token 上
+        match &token_row {
+            TokenRow::Space(text)
+            | TokenRow::Comment {
+                text, block: true, ..
+            } => {
+                self.line_break = self.line_break || text.contains(is_line_terminator);
+                return Some(Ok(Token::new(token_row, position)));
+            }
+            TokenRow::Comment { .. } => return Some(Ok(Token::new(token_row, position))),
+            _ => {}
+        }
+        self.goal = Goal::Div;
+        let mut token = Token::new(token_row, position);
+        token.line_break = std::mem::take(&mut self.line_break);
+        Some(Ok(token))
     }
 }
 
@@ -343,3 +1040,330 @@ impl<'a> Iterator for Scanner<'a> {
         self.scan()
     }
 }
+
+fn digits_value(digits: &str, radix: u32) -> f64 {
+    //十进制交给 f64 解析；2、8、16 进制先精确地累加到 u128，放不下的低位只记录是否非零，
+    //最后只舍入一次，逐位累加 f64 会多次舍入
+    if radix == 10 {
+        return digits.parse().unwrap();
+    }
+    let bits = radix.trailing_zeros();
+    let mut value: u128 = 0;
+    let mut shift = 0;
+    let mut sticky = false;
+    for ch in digits.chars() {
+        let digit = ch.to_digit(radix).unwrap() as u128;
+        if value >> (128 - bits) == 0 {
+            value = value << bits | digit;
+        } else {
+            shift += bits as i32;
+            sticky |= digit != 0;
+        }
+    }
+    (value | sticky as u128) as f64 * 2f64.powi(shift)
+}
+
+fn push_code_point(value: &mut String, high_surrogate: &mut Option<u32>, code: Option<u32>) {
+    //\uD83D\uDE00 这样的代理对要合并成一个字符，单独的代理项无法放进 String，用 U+FFFD 代替
+    //code 为 None 表示字符串结束，只处理还没有配对的代理项
+    if let (Some(high), Some(low @ 0xDC00..=0xDFFF)) = (*high_surrogate, code) {
+        *high_surrogate = None;
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        value.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+        return;
+    }
+    if high_surrogate.take().is_some() {
+        value.push('\u{FFFD}');
+    }
+    match code {
+        Some(code @ 0xD800..=0xDBFF) => *high_surrogate = Some(code),
+        Some(code) => value.push(char::from_u32(code).unwrap_or('\u{FFFD}')),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //扫描出所有的 token，scan_error 返回第一个错误的描述
+    fn scan(source: &str) -> Vec<TokenRow> {
+        Scanner::new(source)
+            .map(|token| token.unwrap().token)
+            .filter(|token| !matches!(token, TokenRow::Space(_)))
+            .collect()
+    }
+
+    fn scan_error(source: &str) -> String {
+        match Scanner::new(source).find_map(Result::err) {
+            Some(error) => error.describe,
+            None => panic!("{:?} should not scan", source),
+        }
+    }
+
+    #[test]
+    fn comments() {
+        let tokens = scan("a // line\n/* block */ b");
+        assert_eq!(
+            tokens[1],
+            TokenRow::Comment {
+                text: " line".to_string(),
+                block: false,
+                end: Position::new(0, 8),
+            }
+        );
+        assert_eq!(
+            tokens[2],
+            TokenRow::Comment {
+                text: " block ".to_string(),
+                block: true,
+                end: Position::new(1, 10),
+            }
+        );
+        assert_eq!(tokens[3], TokenRow::Identifier("b".to_string()));
+        //块注释中的换行记在下一个 token 上
+        let tokens: Vec<Token> = Scanner::new("a /*\n*/ b").map(Result::unwrap).collect();
+        assert!(tokens.last().unwrap().line_break);
+    }
+
+    #[test]
+    fn line_terminators() {
+        //\r\n 只算一次换行，\r、U+2028、U+2029 也是换行
+        let tokens: Vec<Token> = Scanner::new("a\r\nb\rc\u{2028}d\u{2029}e")
+            .map(Result::unwrap)
+            .filter(|token| !matches!(token.token, TokenRow::Space(_)))
+            .collect();
+        assert!(tokens[1..].iter().all(|token| token.line_break));
+        assert_eq!(tokens[1].position, Position::new(1, 0));
+        assert_eq!(tokens[4].position, Position::new(4, 0));
+        let tokens = scan("a // line\r\nb");
+        assert_eq!(tokens[2], TokenRow::Identifier("b".to_string()));
+    }
+
+    #[test]
+    fn unexpected_char() {
+        assert_eq!(scan_error("x = 1 # 2;"), "unexpected char");
+        let error = Scanner::new("a = 1;\r\nb = 2;\r\nc @")
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(error.position, Position::new(2, 2));
+    }
+
+    #[test]
+    fn unterminated_block_comment() {
+        assert_eq!(scan_error("a /* b"), "unterminated comment");
+        let error = Scanner::new("a\n  /* b *").find_map(Result::err).unwrap();
+        assert_eq!(error.position, Position::new(1, 2));
+    }
+
+    fn string_value(source: &str) -> String {
+        match scan(source).remove(0) {
+            TokenRow::String { value, .. } => value,
+            token => panic!("{:?} is not a string", token),
+        }
+    }
+
+    #[test]
+    fn legacy_octal_escapes() {
+        assert_eq!(string_value(r#""\01""#), "\u{1}");
+        assert_eq!(string_value(r#""\1""#), "\u{1}");
+        assert_eq!(string_value(r#""\101\3770""#), "A\u{ff}0");
+        assert_eq!(string_value(r#""\477\08""#), "'7\08");
+        assert_eq!(string_value(r#""\8\9""#), "89");
+    }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(
+            scan(r#"'a\'b'"#),
+            vec![TokenRow::String {
+                value: "a'b".to_string(),
+                raw: r#"'a\'b'"#.to_string(),
+            }]
+        );
+        assert_eq!(
+            string_value(r#""\n\t\r\b\f\v\0""#),
+            "\n\t\r\u{8}\u{c}\u{b}\0"
+        );
+        assert_eq!(string_value(r#""\x41B\u{43}\u{1F600}""#), "ABC😀");
+        assert_eq!(string_value("'a\\\nb'"), "ab");
+        //代理对合并成一个字符，单独的代理项用 U+FFFD 代替
+        assert_eq!(string_value(r#""\uD83D\uDE00""#), "😀");
+        assert_eq!(string_value(r#""\uD83Dx""#), "\u{FFFD}x");
+        assert_eq!(string_value(r#""\uDE00""#), "\u{FFFD}");
+    }
+
+    #[test]
+    fn invalid_string() {
+        assert_eq!(scan_error("'abc"), "unterminated string literal");
+        assert_eq!(scan_error("\"a\nb\""), "unterminated string literal");
+        assert_eq!(
+            scan_error(r#""\x4""#),
+            "invalid hexadecimal escape sequence"
+        );
+        assert_eq!(scan_error(r#""\u12""#), "invalid unicode escape sequence");
+        assert_eq!(
+            scan_error(r#""\u{110000}""#),
+            "invalid unicode escape sequence"
+        );
+    }
+
+    fn number_value(source: &str) -> f64 {
+        match scan(source).remove(0) {
+            TokenRow::Digital { value, .. } => value,
+            token => panic!("{:?} is not a number", token),
+        }
+    }
+
+    #[test]
+    fn numeric_literals() {
+        assert_eq!(number_value("2.25"), 2.25);
+        assert_eq!(number_value(".5"), 0.5);
+        assert_eq!(number_value("5."), 5.0);
+        assert_eq!(number_value("1e-3"), 0.001);
+        assert_eq!(number_value("2E+2"), 200.0);
+        assert_eq!(number_value("0xFF"), 255.0);
+        assert_eq!(number_value("0o17"), 15.0);
+        assert_eq!(number_value("0b101"), 5.0);
+        assert_eq!(number_value("017"), 15.0);
+        assert_eq!(number_value("019"), 19.0);
+        assert_eq!(number_value("1_000.000_1"), 1000.0001);
+        assert_eq!(
+            scan("1_000"),
+            vec![TokenRow::Digital {
+                value: 1000.0,
+                raw: "1_000".to_string(),
+            }]
+        );
+        assert_eq!(scan("10n"), vec![TokenRow::BigInt("10n".to_string())]);
+        assert_eq!(scan("0x1_Fn"), vec![TokenRow::BigInt("0x1_Fn".to_string())]);
+    }
+
+    #[test]
+    fn numeric_literal_precision() {
+        //超过 2^53 时只在最后舍入一次
+        assert_eq!(number_value("88656776766428817507"), 8.865677676642882e19);
+        assert_eq!(number_value("088656776766428817507"), 8.865677676642882e19);
+        assert_eq!(
+            number_value("0x4e4580d1bdc90220c8e8bfac"),
+            2.422385497957311e28
+        );
+        assert_eq!(
+            number_value("0o5773735156164510053612"),
+            5.526695074886532e19
+        );
+        //超过 128 位时，舍去的低位也会影响舍入
+        let hex = format!("0x1{}8{}1", "0".repeat(13), "0".repeat(30));
+        assert_eq!(number_value(&hex), 1.5324955408658892e54);
+    }
+
+    #[test]
+    fn invalid_numeric_literals() {
+        assert_eq!(scan_error("1__0"), "invalid numeric separator");
+        assert_eq!(scan_error("1_"), "invalid numeric separator");
+        assert_eq!(scan_error("0x_1"), "invalid numeric separator");
+        assert_eq!(scan_error("1e"), "invalid numeric literal");
+        assert_eq!(scan_error("0x"), "invalid numeric literal");
+        let describe = "identifier starts immediately after numeric literal";
+        assert_eq!(scan_error("1.5n"), describe);
+        assert_eq!(scan_error("3in"), describe);
+        assert_eq!(scan_error("0b12"), describe);
+    }
+
+    fn part(cooked: Option<&str>, raw: &str, end: (usize, usize)) -> TemplatePart {
+        TemplatePart {
+            cooked: cooked.map(str::to_string),
+            raw: raw.to_string(),
+            end: Position::new(end.0, end.1),
+        }
+    }
+
+    #[test]
+    fn template_parts() {
+        assert_eq!(
+            scan("`a\\n`"),
+            vec![TokenRow::TemplateString(part(Some("a\n"), "a\\n", (0, 4)))]
+        );
+        //${ } 中的 {} 不会结束替换，嵌套的模板有自己的 head 和 tail
+        let tokens = scan("`x${ {b: 1} }y${`in${c}`}z`");
+        assert_eq!(
+            tokens[0],
+            TokenRow::TemplateHead(part(Some("x"), "x", (0, 3)))
+        );
+        assert_eq!(tokens[1], TokenRow::LeftBrace);
+        assert_eq!(tokens[5], TokenRow::RightBrace);
+        assert_eq!(
+            tokens[6],
+            TokenRow::TemplateMiddle(part(Some("y"), "y", (0, 15)))
+        );
+        assert_eq!(
+            tokens[7],
+            TokenRow::TemplateHead(part(Some("in"), "in", (0, 20)))
+        );
+        assert_eq!(
+            tokens[9],
+            TokenRow::TemplateTail(part(Some(""), "", (0, 23)))
+        );
+        assert_eq!(
+            tokens[10],
+            TokenRow::TemplateTail(part(Some("z"), "z", (0, 26)))
+        );
+        assert_eq!(tokens.len(), 11);
+    }
+
+    #[test]
+    fn template_escapes() {
+        //非法转义不是扫描错误，只是没有 cooked 值
+        assert_eq!(
+            scan("`\\u{g}\\01`"),
+            vec![TokenRow::TemplateString(part(None, "\\u{g}\\01", (0, 9)))]
+        );
+        assert_eq!(
+            scan("`a\r\nb`"),
+            vec![TokenRow::TemplateString(part(Some("a\nb"), "a\nb", (1, 1)))]
+        );
+        assert_eq!(scan_error("`a${b}c"), "unterminated template literal");
+    }
+
+    fn scan_regex(source: &str) -> Result<TokenRow, ScanError> {
+        let mut scanner = Scanner::new(source);
+        scanner.set_goal(Goal::RegExp);
+        scanner.next().unwrap().map(|token| token.token)
+    }
+
+    fn regex(pattern: &str, flags: &str) -> TokenRow {
+        TokenRow::RegExp {
+            pattern: pattern.to_string(),
+            flags: flags.to_string(),
+        }
+    }
+
+    #[test]
+    fn regex_literals() {
+        assert_eq!(scan_regex("/ab+c/gi").unwrap(), regex("ab+c", "gi"));
+        //[] 中的 / 不会结束正则，\/ 也不会
+        assert_eq!(scan_regex("/[/]\\//").unwrap(), regex("[/]\\/", ""));
+        assert_eq!(scan_regex("/[//]/dv").unwrap(), regex("[//]", "dv"));
+        //goal 只对下一个 token 有效
+        let mut scanner = Scanner::new("/a/ /b/");
+        scanner.set_goal(Goal::RegExp);
+        let tokens: Vec<TokenRow> = scanner.map(|token| token.unwrap().token).collect();
+        assert_eq!(tokens[0], regex("a", ""));
+        assert_eq!(tokens[2], TokenRow::Div);
+    }
+
+    #[test]
+    fn invalid_regex_literals() {
+        let describe = |source| scan_regex(source).unwrap_err().describe;
+        assert_eq!(describe("/a"), "unterminated regular expression literal");
+        assert_eq!(
+            describe("/[/\n]/"),
+            "unterminated regular expression literal"
+        );
+        assert_eq!(describe("/a/x"), "invalid regular expression flags");
+        assert_eq!(describe("/a/gg"), "invalid regular expression flags");
+        assert_eq!(describe("/a/uv"), "invalid regular expression flags");
+        assert_eq!(describe("/a/gé"), "invalid regular expression flags");
+        assert_eq!(describe("/a/g$"), "invalid regular expression flags");
+    }
+}
@@ -1,10 +1,15 @@
 pub fn is_space(ch: char) -> bool {
     match ch {
-        ' ' | '\t' | '\n' => true,
-        _ => false,
+        ' ' | '\t' | '\u{b}' | '\u{c}' | '\u{a0}' | '\u{feff}' => true,
+        _ => is_line_terminator(ch),
     }
 }
 
+pub fn is_line_terminator(ch: char) -> bool {
+    //\r\n 由 \r 和 \n 两个字符组成，分别都是换行符
+    matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
 pub fn is_digital(ch: char) -> bool {
     match ch {
         '0'..='9' => true,
@@ -12,6 +17,10 @@ pub fn is_digital(ch: char) -> bool {
     }
 }
 
+pub fn is_hex_digital(ch: char) -> bool {
+    ch.is_ascii_hexdigit()
+}
+
 pub fn is_alpha(ch: char) -> bool {
     //判断是否为字母或者下划线
     match ch {
@@ -25,3 +34,8 @@ pub fn is_alpha(ch: char) -> bool {
 pub fn is_alphadigital(ch: char) -> bool {
     is_alpha(ch) || is_digital(ch)
 }
+
+pub fn is_identifier_part(ch: char) -> bool {
+    //标识符中可以出现的字符，包括非 ASCII 的字母和数字
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}